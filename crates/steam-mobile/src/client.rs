@@ -11,6 +11,7 @@ use reqwest::redirect::Policy;
 use reqwest::{Client, Method, Response, Url};
 use scraper::Html;
 use serde::Serialize;
+use steamid_parser::SteamID;
 use tracing::{debug, info, warn};
 
 use crate::errors::{AuthError, LinkerError, LoginError};
@@ -24,7 +25,9 @@ use crate::web_handler::steam_guard_linker::{
     finalize, validate_phone_number, AddAuthenticatorStep, STEAM_ADD_PHONE_CATCHUP_SECS,
 };
 use crate::web_handler::{cache_resolve, confirmations_retrieve_all, confirmations_send, parental_unlock};
-use crate::{CachedInfo, ConfirmationMethod, MobileAuthFile, User, STEAM_COMMUNITY_HOST};
+use crate::{
+    CachedInfo, ConfirmationMethod, MobileAuthFile, User, STEAM_COMMUNITY_HOST, STEAM_HELP_HOST, STEAM_STORE_HOST,
+};
 
 #[derive(Debug)]
 /// Main authenticator. We use it to spawn and act as our "mobile" client.
@@ -55,6 +58,64 @@ impl SteamAuthenticator {
         }
     }
 
+    /// Constructs a Steam Authenticator from the cookies of a session that was logged in elsewhere, such as by a
+    /// separate authentication service, without logging in again.
+    ///
+    /// `steam_login_secure` is the value of the `steamLoginSecure` cookie, that starts with the account SteamID64.
+    /// The API key is only cached by [SteamAuthenticator::login], so pass it if Steam Web API methods are needed.
+    /// Confirmations still need the mobile authenticator file linked to `user`.
+    pub fn from_cookies(
+        user: User,
+        session_id: &str,
+        steam_login_secure: &str,
+        api_key: Option<String>,
+    ) -> Result<Self, LoginError> {
+        let steam_login_secure = steam_login_secure.replace("||", "%7C%7C");
+        let steamid = steam_login_secure
+            .split("%7C%7C")
+            .next()
+            .and_then(|steamid| steamid.parse::<u64>().ok())
+            .ok_or_else(|| {
+                LoginError::GeneralFailure("The steamLoginSecure cookie does not start with a SteamID64.".to_string())
+            })?;
+
+        let authenticator = Self::new(user);
+        {
+            let mut cookie_jar = authenticator.client.cookie_store.borrow_mut();
+            for host in &[STEAM_COMMUNITY_HOST, STEAM_HELP_HOST, STEAM_STORE_HOST] {
+                cookie_jar.add_original(
+                    Cookie::build("steamLoginSecure", steam_login_secure.clone())
+                        .domain(*host)
+                        .path("/")
+                        .finish(),
+                );
+                cookie_jar.add_original(
+                    Cookie::build("sessionid", session_id.to_string())
+                        .domain(*host)
+                        .path("/")
+                        .finish(),
+                );
+            }
+        }
+        {
+            let mut cached_data = authenticator.cached_data.borrow_mut();
+            cached_data.steamid = Some(SteamID::from_steam64(steamid));
+            if let Some(api_key) = api_key {
+                cached_data.set_api_key(api_key);
+            }
+        }
+
+        Ok(authenticator)
+    }
+
+    /// Routes every request of this authenticator through the proxy at `proxy_url`, e.g. "socks5://127.0.0.1:1080".
+    ///
+    /// Must be set before login, since Steam ties the session to the address it was created from.
+    pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self, reqwest::Error> {
+        self.client.inner_http_client = MobileClient::build_mobile_client(Some(reqwest::Proxy::all(proxy_url)?))?;
+        Ok(self)
+    }
+
     /// Returns current user API Key. Need to login first.
     pub fn api_key(&self) -> Option<String> {
         let api_key;
@@ -65,6 +126,11 @@ impl SteamAuthenticator {
         api_key
     }
 
+    /// Returns the SteamID of the logged in user. Need to login first.
+    pub fn steamid(&self) -> Option<SteamID> {
+        self.cached_data.borrow().steamid.clone()
+    }
+
     fn client(&self) -> &MobileClient {
         &self.client
     }
@@ -233,9 +299,7 @@ impl SteamAuthenticator {
             .steam_id()
             .expect("Failed to retrieve cached SteamID. Are you logged in?");
 
-        confirmations_send(&self.client, &self.user, steamid, operation, confirmations.0)
-            .await
-            .map_err(|e| e.into())
+        confirmations_send(&self.client, &self.user, steamid, operation, confirmations.0).await
     }
 
     /// You can request custom operations for any Steam operation that requires logging in.
@@ -391,6 +455,11 @@ impl MobileClient {
 
     /// Initiate mobile client with default headers
     fn init_mobile_client() -> Client {
+        Self::build_mobile_client(None).unwrap()
+    }
+
+    /// Builds the mobile client with default headers, sending every request through `proxy` if set.
+    fn build_mobile_client(proxy: Option<reqwest::Proxy>) -> Result<Client, reqwest::Error> {
         let user_agent = "Mozilla/5.0 (Linux; U; Android 4.1.1; en-us; Google Nexus 4 - 4.1.1 - API 16 - 768x1280 \
                           Build/JRO03S) AppleWebKit/534.30 (KHTML, like Gecko) Version/4.0 Mobile Safari/534.30";
         let mut default_headers = HeaderMap::new();
@@ -406,14 +475,18 @@ impl MobileClient {
             "com.valvesoftware.android.steam.community".parse().unwrap(),
         );
 
-        reqwest::Client::builder()
+        let mut builder = reqwest::Client::builder()
             .user_agent(user_agent)
             .cookie_store(true)
             .redirect(Policy::limited(5))
             .default_headers(default_headers)
-            .referer(false)
-            .build()
-            .unwrap()
+            .referer(false);
+
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(proxy);
+        }
+
+        builder.build()
     }
 }
 
@@ -425,3 +498,36 @@ impl Default for MobileClient {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn authenticator_from_cookies() {
+        let user = User::new("basinga".to_string(), "hunter2".to_string());
+        let authenticator = SteamAuthenticator::from_cookies(
+            user,
+            "2d4bd9b3e2c5",
+            "76561198040191316||A1B2C3",
+            Some("KEY".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            authenticator
+                .dump_cookie(STEAM_COMMUNITY_HOST, "steamLoginSecure")
+                .as_deref(),
+            Some("76561198040191316%7C%7CA1B2C3")
+        );
+        assert_eq!(
+            authenticator.dump_cookie(STEAM_STORE_HOST, "sessionid").as_deref(),
+            Some("2d4bd9b3e2c5")
+        );
+        assert_eq!(authenticator.api_key().as_deref(), Some("KEY"));
+        assert_eq!(authenticator.cached_data.borrow().steam_id(), Some(76561198040191316));
+
+        let user = User::new("basinga".to_string(), "hunter2".to_string());
+        assert!(SteamAuthenticator::from_cookies(user, "2d4bd9b3e2c5", "A1B2C3", None).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use steam_language_gen::generated::enums::EResult;
 use thiserror::Error;
 
 /// Main error type that SteamAuthenticator uses.
@@ -18,6 +19,9 @@ pub enum AuthError {
     Login(#[from] LoginError),
     #[error(transparent)]
     MobileAuthFile(#[from] MobileAuthFileError),
+    /// Steam refused to process the confirmations, with `EResult`.
+    #[error("Steam rejected the confirmation with `{0:?}`.")]
+    ConfirmationRejected(EResult),
     #[error(transparent)]
     HttpError(#[from] reqwest::Error),
 }
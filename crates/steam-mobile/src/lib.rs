@@ -20,7 +20,8 @@ use std::path::PathBuf;
 
 use const_format::concatcp;
 /// re-export
-pub use reqwest::{header::HeaderMap, Error as HttpError, Method, Url};
+pub use reqwest::header::{HeaderMap, HeaderValue};
+pub use reqwest::{Error as HttpError, Method, StatusCode, Url};
 use serde::{Deserialize, Serialize};
 use steam_totp::Secret;
 use steamid_parser::SteamID;
@@ -29,7 +30,9 @@ use uuid::Uuid;
 
 use crate::errors::{AuthError, MobileAuthFileError};
 use crate::utils::read_from_disk;
-pub use web_handler::confirmation::{ConfirmationMethod, Confirmations, EConfirmationType};
+pub use web_handler::confirmation::{
+    Confirmation, ConfirmationDetails, ConfirmationMethod, Confirmations, EConfirmationType,
+};
 pub use web_handler::steam_guard_linker::AddAuthenticatorStep;
 
 pub mod client;
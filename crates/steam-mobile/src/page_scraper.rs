@@ -23,19 +23,25 @@ pub(crate) fn confirmation_retrieve(confirmation_html: Html) -> Option<Vec<Confi
                 .attr("data-type")
                 .map_or(EConfirmationType::Unknown, |s| EConfirmationType::from_str(s).unwrap());
 
-            let tradeoffer_id = if let EConfirmationType::Trade = confirmation_type {
-                element.value().attr("data-creator").map(|s| i64::from_str(s).unwrap())
-            } else {
-                None
+            // The creator is the trade offer of trade confirmations, and the listing of market confirmations.
+            let creator = element.value().attr("data-creator").map(|s| i64::from_str(s).unwrap());
+            let details = match confirmation_type {
+                EConfirmationType::Trade => creator.map(|id| ConfirmationDetails {
+                    trade_offer_id: Some(id),
+                    market_listing_id: None,
+                }),
+                EConfirmationType::Market => creator.map(|id| ConfirmationDetails {
+                    trade_offer_id: None,
+                    market_listing_id: Some(id),
+                }),
+                _ => None,
             };
 
             Confirmation {
                 id: element.value().attr("data-confid").unwrap().to_string(),
                 key: element.value().attr("data-key").unwrap().to_string(),
                 kind: confirmation_type,
-                details: tradeoffer_id.map(|id| ConfirmationDetails {
-                    trade_offer_id: Some(id),
-                }),
+                details,
             }
         })
         .collect::<Vec<Confirmation>>();
@@ -66,6 +72,7 @@ pub(crate) fn confirmation_details_single(confirmation_details_html: Html) -> Co
 
         ConfirmationDetails {
             trade_offer_id: Some(i64::from_str(tradeofferid_parsed).unwrap()),
+            market_listing_id: None,
         }
     } else if confirmation_details_html
         .select(&market_selector)
@@ -73,7 +80,10 @@ pub(crate) fn confirmation_details_single(confirmation_details_html: Html) -> Co
         .peek()
         .is_some()
     {
-        ConfirmationDetails { trade_offer_id: None }
+        ConfirmationDetails {
+            trade_offer_id: None,
+            market_listing_id: None,
+        }
     } else {
         unimplemented!()
         // ConfirmationDetails {
@@ -142,8 +152,13 @@ mod tests {
     #[test]
     fn test_multi_confirmation() {
         let api_doc = Html::parse_document(include_str!("../assets/multi_confirmation.html"));
-        let confirmations = confirmation_retrieve(api_doc);
-        assert!(confirmations.is_some());
+        let confirmations = confirmation_retrieve(api_doc).unwrap();
+
+        let listing = confirmations
+            .iter()
+            .find(|confirmation| confirmation.kind == EConfirmationType::Market)
+            .unwrap();
+        assert_eq!(listing.details.unwrap().market_listing_id, Some(4417411197346325411));
     }
 
     #[test]
@@ -78,6 +78,16 @@ pub struct BooleanResponse {
     pub success: bool,
 }
 
+/// Response of accepting or denying confirmations.
+///
+/// Steam may refuse them right away, even though they were listed, in which case `eresult` tells why.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ConfirmationOperationResponse {
+    pub success: bool,
+    #[serde(default)]
+    pub eresult: Option<EResult>,
+}
+
 #[derive(Serialize, Debug, Clone)]
 pub struct ParentalUnlockRequest<'a> {
     /// Parental Unlock Code
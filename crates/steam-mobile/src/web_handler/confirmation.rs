@@ -3,6 +3,7 @@ use std::str::FromStr;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
 #[derive(Default, Debug)]
 /// FIXME: describe confirmations..
@@ -23,6 +24,8 @@ pub struct Confirmation {
 pub struct ConfirmationDetails {
     /// ID of the trade offer. Has a value if EConfirmationType::Trade
     pub trade_offer_id: Option<i64>,
+    /// ID of the market listing. Has a value if EConfirmationType::Market
+    pub market_listing_id: Option<i64>,
 }
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq, FromPrimitive)]
@@ -92,6 +95,9 @@ impl Confirmations {
     /// For example, you could have them coming from some other service, or elsewhere and you can
     /// easily filter them.
     ///
+    /// Confirmations are left in the order of `trade_offer_ids`. Steam occasionally lists two confirmations for the
+    /// same trade offer, and every one of them is kept, so they are all processed.
+    ///
     /// # Example
     /// ```no_run
     /// # use steam_mobile::{ConfirmationMethod, EConfirmationType, User};
@@ -110,13 +116,26 @@ impl Confirmations {
     where
         T: AsRef<[i64]>,
     {
-        self.0.retain(|c| {
-            if let Some(conf_details) = c.details {
-                let trade_offer_id = conf_details.trade_offer_id.unwrap();
-                return trade_offer_ids.as_ref().iter().any(|&id| id == trade_offer_id);
+        let trade_offer_ids = trade_offer_ids.as_ref();
+        let position_of = |c: &Confirmation| {
+            let trade_offer_id = c.details.and_then(|details| details.trade_offer_id)?;
+            trade_offer_ids.iter().position(|&id| id == trade_offer_id)
+        };
+
+        self.0.retain(|c| position_of(c).is_some());
+        self.0.sort_by_key(|c| position_of(c));
+
+        let mut previous_position = None;
+        for confirmation in &self.0 {
+            let position = position_of(confirmation);
+            if position == previous_position {
+                warn!(
+                    "Found more than one confirmation for trade offer {}.",
+                    trade_offer_ids[position.unwrap()]
+                );
             }
-            false
-        });
+            previous_position = position;
+        }
     }
 
     pub fn has_trade_offer_id(&self, trade_offer_id: i64) -> bool {
@@ -172,6 +191,7 @@ mod tests {
             kind: EConfirmationType::Trade,
             details: Some(ConfirmationDetails {
                 trade_offer_id: Some(4009687284),
+                market_listing_id: None,
             }),
         });
         vec.push(Confirmation {
@@ -180,6 +200,7 @@ mod tests {
             kind: EConfirmationType::Trade,
             details: Some(ConfirmationDetails {
                 trade_offer_id: Some(4000980011),
+                market_listing_id: None,
             }),
         });
         vec.push(Confirmation {
@@ -188,6 +209,7 @@ mod tests {
             kind: EConfirmationType::Trade,
             details: Some(ConfirmationDetails {
                 trade_offer_id: Some(4000793103),
+                market_listing_id: None,
             }),
         });
         vec.push(Confirmation {
@@ -224,9 +246,11 @@ mod tests {
 
         let details_0 = ConfirmationDetails {
             trade_offer_id: Some(first),
+            market_listing_id: None,
         };
         let details_1 = ConfirmationDetails {
             trade_offer_id: Some(second),
+            market_listing_id: None,
         };
 
         confirmations.filter_by_trade_offer_ids(tradeoffer_id);
@@ -234,4 +258,22 @@ mod tests {
         assert_eq!(confirmations.0.get(1).unwrap().details, Some(details_1));
         assert_eq!(confirmations.0.get(2), None);
     }
+
+    #[test]
+    fn filter_duplicated_trade_offer_id() {
+        let mut confirmations = get_confirmations();
+        confirmations.0.push(Confirmation {
+            id: "7676451140".to_string(),
+            key: "18064583892738866190".to_string(),
+            kind: EConfirmationType::Trade,
+            details: Some(ConfirmationDetails {
+                trade_offer_id: Some(4009687284),
+                market_listing_id: None,
+            }),
+        });
+
+        confirmations.filter_by_trade_offer_ids(vec![4000980011, 4009687284]);
+        let ids = confirmations.0.iter().map(|c| c.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["7652515663", "7676451136", "7676451140"]);
+    }
 }
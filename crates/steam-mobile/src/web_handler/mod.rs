@@ -11,10 +11,10 @@ use steam_totp::Time;
 use tracing::{debug, trace, warn};
 
 use crate::client::MobileClient;
-use crate::errors::{ApiKeyError, LoginError};
+use crate::errors::{ApiKeyError, AuthError, LoginError};
 use crate::page_scraper::{api_key_resolve_status, confirmation_details_single, confirmation_retrieve};
 use crate::types::{
-    ApiKeyRegisterRequest, BooleanResponse, ConfirmationDetailsResponse, ConfirmationMultiAcceptRequest,
+    ApiKeyRegisterRequest, ConfirmationDetailsResponse, ConfirmationMultiAcceptRequest, ConfirmationOperationResponse,
     ParentalUnlockRequest, ParentalUnlockResponse,
 };
 use crate::utils::{dump_cookie_from_header, dump_cookies_by_name};
@@ -142,7 +142,7 @@ pub(crate) async fn confirmations_send(
     steamid: u64,
     method: ConfirmationMethod,
     confirmations: Vec<Confirmation>,
-) -> Result<(), reqwest::Error> {
+) -> Result<(), AuthError> {
     let url = format!("{}/mobileconf/multiajaxop", STEAM_COMMUNITY_BASE);
     let operation = method.value();
 
@@ -165,18 +165,17 @@ pub(crate) async fn confirmations_send(
         ..Default::default()
     };
 
-    client
+    let response = client
         .request_with_session_guard(url, Method::POST, None, Some(request))
         .await?
-        .json::<BooleanResponse>()
+        .json::<ConfirmationOperationResponse>()
         .await?;
 
-    // FIXME: Error Catching
-    // if response.success {
-    //     Ok(())
-    // }
-
-    Ok(())
+    match response.eresult {
+        Some(eresult) if eresult != EResult::OK => Err(AuthError::ConfirmationRejected(eresult)),
+        _ if !response.success => Err(AuthError::ConfirmationRejected(EResult::Fail)),
+        _ => Ok(()),
+    }
 }
 
 /// Retrieve all confirmations for user, opting between retrieving details or not.
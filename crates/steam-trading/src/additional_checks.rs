@@ -1,9 +1,12 @@
+use crate::errors::ConfirmationError;
 use crate::{OfferError, TradeError, TryFutureExt, TRADEOFFER_BASE};
 use scraper::{Html, Selector};
 use steam_mobile::client::SteamAuthenticator;
-use steam_mobile::Method;
+use steam_mobile::{HeaderMap, Method};
 use steamid_parser::SteamID;
 
+const STEAM_GUARD_MANAGE_URL: &str = "https://store.steampowered.com/twofactor/manage";
+
 fn is_steam_guard_error(document: &str) -> bool {
     let doc = Html::parse_document(document);
 
@@ -23,8 +26,14 @@ fn is_steam_guard_error(document: &str) -> bool {
     false
 }
 
+/// Steam sends its login page instead of the expected response if the session is no longer valid.
+pub(crate) fn is_login_wall(document: &str) -> bool {
+    document.contains("g_steamID = false;") || document.contains("<title>Sign In</title>")
+}
+
 pub async fn check_steam_guard_error(
     authenticator: &SteamAuthenticator,
+    headers: HeaderMap,
     steamid: SteamID,
     token: &str,
 ) -> Result<(), TradeError> {
@@ -36,7 +45,7 @@ pub async fn check_steam_guard_error(
     );
 
     let response = authenticator
-        .request_custom_endpoint(endpoint, Method::GET, None, None::<&u8>)
+        .request_custom_endpoint(endpoint, Method::GET, Some(headers), None::<&u8>)
         .and_then(|x| x.text())
         .await?;
 
@@ -46,3 +55,60 @@ pub async fn check_steam_guard_error(
         Ok(())
     }
 }
+
+/// Steam only offers to remove the authenticator at the Steam Guard management page if the mobile one is active.
+fn is_mobile_guard_enabled(document: &str) -> bool {
+    document.contains("Remove Authenticator")
+}
+
+pub async fn check_mobile_guard_enabled(
+    authenticator: &SteamAuthenticator,
+    headers: HeaderMap,
+) -> Result<(), TradeError> {
+    let response = authenticator
+        .request_custom_endpoint(
+            STEAM_GUARD_MANAGE_URL.to_string(),
+            Method::GET,
+            Some(headers),
+            None::<&u8>,
+        )
+        .and_then(|x| x.text())
+        .await?;
+
+    if is_mobile_guard_enabled(&response) {
+        Ok(())
+    } else {
+        Err(ConfirmationError::MobileGuardNotEnabled.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn login_wall_response() -> &'static str {
+        r#"<!DOCTYPE html>
+<html class=" responsive" lang="en">
+<head>
+    <meta http-equiv="Content-Type" content="text/html; charset=UTF-8">
+    <title>Sign In</title>
+    <script type="text/javascript">
+        g_sessionID = "1f2d4cb5ea3a7b2c1fd8a1c3";
+        g_steamID = false;
+    </script>
+</head>
+<body class="responsive_page">
+    <div class="login_modal loginAuthCodeModal">
+        <form name="logon" action="https://steamcommunity.com/login/dologin/" method="POST">
+        </form>
+    </div>
+</body>
+</html>"#
+    }
+
+    #[test]
+    fn login_wall() {
+        assert!(is_login_wall(login_wall_response()));
+        assert!(!is_login_wall(r#"{"tradeofferid":"4112828817"}"#));
+    }
+}
@@ -1,3 +1,7 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
 use tappet::response_types::{
     CEcon_Asset, GetTradeHistoryResponse, GetTradeOfferResponse, GetTradeOffersResponse, TradeHistory_Trade,
     TradeHistory_TradedAsset, TradeOffer_Trade,
@@ -8,6 +12,31 @@ pub trait HasAssets {
     fn every_asset(self) -> Vec<Self::Asset>;
 }
 
+pub trait InvolvedAppids {
+    /// Returns every appid that has at least one asset being traded.
+    fn involved_appids(&self) -> HashSet<u32>;
+}
+
+pub trait ContentFingerprint {
+    /// Returns a hash of what is being traded and with whom.
+    ///
+    /// Two offers with the same partner and the same assets on each side have the same fingerprint, regardless of the
+    /// order Steam lists the assets in. It is only meant to be compared within the same build of this crate.
+    fn content_fingerprint(&self) -> u64;
+}
+
+pub trait ValueDelta {
+    /// Returns the value of what is received minus the value of what is given, as priced by `price_fn`.
+    ///
+    /// `price_fn` prices a single unit of an asset, and is multiplied by the amount being traded.
+    fn value_delta<F: Fn(&CEcon_Asset) -> f64>(&self, price_fn: F) -> f64;
+}
+
+pub trait ItemCounts {
+    /// Returns how many items would be given and received, in this order, counting the amount of stackable items.
+    fn item_counts(&self) -> (usize, usize);
+}
+
 pub trait FilterBy<C> {
     fn filter_by<T: Fn(&C) -> bool>(self, filter_fn: T) -> Vec<C>;
 
@@ -127,3 +156,91 @@ impl FilterBy<TradeOffer_Trade> for GetTradeOfferResponse {
         unimplemented!()
     }
 }
+
+impl HasAssets for TradeOffer_Trade {
+    type Asset = CEcon_Asset;
+
+    /// Returns every asset that a trade offer has. To give or to receive.
+    fn every_asset(self) -> Vec<CEcon_Asset> {
+        let mut offer_assets = vec![];
+
+        if let Some(assets) = self.items_to_give {
+            offer_assets.push(assets);
+        }
+        if let Some(assets) = self.items_to_receive {
+            offer_assets.push(assets);
+        }
+
+        offer_assets.into_iter().flatten().collect()
+    }
+}
+
+impl InvolvedAppids for TradeOffer_Trade {
+    fn involved_appids(&self) -> HashSet<u32> {
+        self.items_to_give
+            .iter()
+            .chain(self.items_to_receive.iter())
+            .flatten()
+            .map(|asset| asset.appid as u32)
+            .collect()
+    }
+}
+
+impl ValueDelta for TradeOffer_Trade {
+    fn value_delta<F: Fn(&CEcon_Asset) -> f64>(&self, price_fn: F) -> f64 {
+        let value_of = |assets: &Option<Vec<CEcon_Asset>>| -> f64 {
+            assets
+                .iter()
+                .flatten()
+                .map(|asset| price_fn(asset) * asset.amount as f64)
+                .sum()
+        };
+
+        value_of(&self.items_to_receive) - value_of(&self.items_to_give)
+    }
+}
+
+impl ItemCounts for TradeOffer_Trade {
+    fn item_counts(&self) -> (usize, usize) {
+        let count_of = |assets: &Option<Vec<CEcon_Asset>>| -> usize {
+            assets.iter().flatten().map(|asset| asset.amount.max(0) as usize).sum()
+        };
+
+        (count_of(&self.items_to_give), count_of(&self.items_to_receive))
+    }
+}
+
+impl ContentFingerprint for TradeOffer_Trade {
+    fn content_fingerprint(&self) -> u64 {
+        let assets_of = |assets: &Option<Vec<CEcon_Asset>>| -> Vec<(i64, i64, i64, i64)> {
+            assets
+                .iter()
+                .flatten()
+                .map(|asset| (asset.appid, asset.contextid, asset.assetid, asset.amount))
+                .collect()
+        };
+
+        fingerprint(
+            self.accountid_other,
+            assets_of(&self.items_to_give),
+            assets_of(&self.items_to_receive),
+        )
+    }
+}
+
+/// Hashes the partner accountid along with the `(appid, contextid, assetid, amount)` of the assets on each side,
+/// regardless of their order.
+pub(crate) fn fingerprint(
+    accountid_other: u64,
+    mut given: Vec<(i64, i64, i64, i64)>,
+    mut received: Vec<(i64, i64, i64, i64)>,
+) -> u64 {
+    given.sort_unstable();
+    received.sort_unstable();
+
+    let mut hasher = DefaultHasher::new();
+    accountid_other.hash(&mut hasher);
+    given.hash(&mut hasher);
+    received.hash(&mut hasher);
+    hasher.finish()
+}
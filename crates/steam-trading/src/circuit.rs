@@ -0,0 +1,189 @@
+//! Circuit breaker that stops sending requests while Steam is down.
+//!
+//! During an outage every request fails, usually after a long wait. Once `CircuitBreaker` sees enough consecutive
+//! failures it opens, and requests fail right away with `TradeError::CircuitOpen` until a cooldown passes. A single
+//! request is then let through to probe whether Steam is back, closing the circuit if it succeeds, or opening it again
+//! for twice as long if it does not.
+
+use std::time::{Duration, Instant};
+
+use tappet::errors::SteamAPIError;
+
+use crate::errors::{OfferError, TradeError};
+
+/// Consecutive failures that open the circuit.
+const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// Time the circuit stays open after it first opens.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Longest the circuit stays open, however many probes failed.
+const DEFAULT_MAX_COOLDOWN: Duration = Duration::from_secs(600);
+
+/// State of a `CircuitBreaker`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CircuitState {
+    /// Requests are sent as usual.
+    Closed,
+    /// Requests fail right away, until the cooldown passes.
+    Open,
+    /// The cooldown passed, and the next request probes whether Steam recovered.
+    HalfOpen,
+}
+
+/// Fails requests fast after repeated Steam failures, backing off exponentially while they keep failing.
+///
+/// Enable it with `SteamTradeManager::with_circuit_breaker`.
+#[derive(Debug, Copy, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    base_cooldown: Duration,
+    max_cooldown: Duration,
+    consecutive_failures: u32,
+    cooldown: Duration,
+    opened_at: Option<Instant>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(DEFAULT_FAILURE_THRESHOLD, DEFAULT_COOLDOWN)
+    }
+}
+
+impl CircuitBreaker {
+    /// Opens the circuit after `failure_threshold` consecutive failures, for `cooldown` at first.
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            base_cooldown: cooldown,
+            max_cooldown: DEFAULT_MAX_COOLDOWN.max(cooldown),
+            consecutive_failures: 0,
+            cooldown,
+            opened_at: None,
+        }
+    }
+
+    /// Caps the cooldown at `max_cooldown` while probes keep failing, instead of ten minutes.
+    pub fn with_max_cooldown(mut self, max_cooldown: Duration) -> Self {
+        self.max_cooldown = max_cooldown.max(self.base_cooldown);
+        self
+    }
+
+    /// Current state of the circuit.
+    pub fn state(&self) -> CircuitState {
+        self.state_at(Instant::now())
+    }
+
+    fn state_at(&self, now: Instant) -> CircuitState {
+        match self.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if now.duration_since(opened_at) < self.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Fails with `TradeError::CircuitOpen` if a request should not be sent at `now`.
+    pub(crate) fn check(&self, now: Instant) -> Result<(), TradeError> {
+        match (self.state_at(now), self.opened_at) {
+            (CircuitState::Open, Some(opened_at)) => Err(TradeError::CircuitOpen {
+                retry_after: self.cooldown - now.duration_since(opened_at),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records the result of a request sent at `now`, opening or closing the circuit.
+    ///
+    /// Only errors that hint at Steam being down count as failures. Any other result closes the circuit.
+    pub(crate) fn record<T>(&mut self, result: &Result<T, TradeError>, now: Instant) {
+        if !result.as_ref().err().map_or(false, is_outage_error) {
+            self.consecutive_failures = 0;
+            self.cooldown = self.base_cooldown;
+            self.opened_at = None;
+            return;
+        }
+
+        if self.state_at(now) == CircuitState::HalfOpen {
+            self.cooldown = (self.cooldown * 2).min(self.max_cooldown);
+            self.opened_at = Some(now);
+            return;
+        }
+
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= self.failure_threshold {
+            self.opened_at = Some(now);
+        }
+    }
+}
+
+fn is_outage_error(error: &TradeError) -> bool {
+    match error {
+        TradeError::Timeout
+        | TradeError::HttpError(_)
+        | TradeError::SteamAPIError(SteamAPIError::HttpError(_))
+        | TradeError::TradeOfferError(OfferError::GeneralFailure(_))
+        | TradeError::TradeOfferError(OfferError::TransientSendFailure) => true,
+        TradeError::SteamAPIError(SteamAPIError::SteamHttpError(status)) => {
+            status.parse::<u16>().map_or(false, |status| status >= 500)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure() -> Result<(), TradeError> {
+        Err(OfferError::GeneralFailure("Steam Response: <html>".to_string()).into())
+    }
+
+    #[test]
+    fn opens_after_threshold_and_backs_off() {
+        let start = Instant::now();
+        let mut circuit = CircuitBreaker::new(3, Duration::from_secs(10));
+
+        (0..2).for_each(|_| circuit.record(&failure(), start));
+        assert_eq!(circuit.state_at(start), CircuitState::Closed);
+
+        circuit.record(&failure(), start);
+        assert_eq!(circuit.state_at(start), CircuitState::Open);
+        assert!(matches!(
+            circuit.check(start + Duration::from_secs(4)),
+            Err(TradeError::CircuitOpen { retry_after }) if retry_after == Duration::from_secs(6)
+        ));
+
+        // the probe fails, so the circuit opens again for twice as long
+        let probe = start + Duration::from_secs(10);
+        assert_eq!(circuit.state_at(probe), CircuitState::HalfOpen);
+        assert!(circuit.check(probe).is_ok());
+        circuit.record(&failure(), probe);
+        assert_eq!(circuit.state_at(probe + Duration::from_secs(19)), CircuitState::Open);
+
+        let probe = probe + Duration::from_secs(20);
+        assert_eq!(circuit.state_at(probe), CircuitState::HalfOpen);
+        circuit.record(&Ok(()), probe);
+        assert_eq!(circuit.state_at(probe), CircuitState::Closed);
+    }
+
+    #[test]
+    fn ignores_errors_unrelated_to_outages() {
+        let now = Instant::now();
+        let mut circuit = CircuitBreaker::new(1, Duration::from_secs(10));
+
+        circuit.record::<()>(&Err(OfferError::OwnAccountLimited.into()), now);
+        assert_eq!(circuit.state_at(now), CircuitState::Closed);
+
+        circuit.record::<()>(&Err(SteamAPIError::SteamHttpError("403".to_string()).into()), now);
+        assert_eq!(circuit.state_at(now), CircuitState::Closed);
+    }
+
+    #[test]
+    fn web_api_server_errors_are_outages() {
+        let now = Instant::now();
+        let mut circuit = CircuitBreaker::new(1, Duration::from_secs(10));
+
+        circuit.record::<()>(&Err(SteamAPIError::SteamHttpError("503".to_string()).into()), now);
+        assert_eq!(circuit.state_at(now), CircuitState::Open);
+    }
+}
@@ -0,0 +1,191 @@
+//! Settings and state shared by `SteamTradeManager` and `OwnedSteamTradeManager`.
+//!
+//! Both managers hold a `ManagerConfig`, and get the same `with_*` builders from `impl_config_builders!`, so a setting
+//! is only added once. `OwnedSteamTradeManager::manager` clones it, and since the state is behind `Rc`, every manager
+//! built from the same owned manager shares it.
+
+use std::cell::{Cell, RefCell};
+#[cfg(feature = "persona")]
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use tappet::SteamAPI;
+
+#[cfg(feature = "time")]
+use crate::time;
+#[cfg(feature = "persona")]
+use crate::Persona;
+use crate::{
+    AdaptivePacing, CircuitBreaker, SupportedApps, DEFAULT_MAX_RESPONSE_BYTES, DEFAULT_USER_AGENT, STANDARD_DELAY,
+};
+
+#[derive(Debug, Clone)]
+pub(crate) struct ManagerConfig {
+    pub(crate) api_client: Rc<RefCell<Option<SteamAPI>>>,
+    /// Set once the account was verified to have mobile confirmations enabled.
+    pub(crate) mobile_guard_verified: Rc<Cell<bool>>,
+    /// When the latest request to Steam Community finished, if any was sent yet.
+    pub(crate) last_request_at: Rc<Cell<Option<Instant>>>,
+    /// Personas already resolved, keyed by SteamID64.
+    #[cfg(feature = "persona")]
+    pub(crate) persona_cache: Rc<RefCell<HashMap<u64, Persona>>>,
+    /// Sent as the `User-Agent` header of every request.
+    pub(crate) user_agent: String,
+    /// Time between checks, when polling Steam for the state of an offer.
+    pub(crate) poll_interval: Duration,
+    /// Responses larger than this are not read, and fail with `TradeError::ResponseTooLarge`.
+    pub(crate) max_response_bytes: usize,
+    /// Proxy url the Steam Web API client is built with.
+    pub(crate) proxy: Option<String>,
+    /// Adapts the delay between requests to Steam latency, instead of the fixed `STANDARD_DELAY`.
+    pub(crate) pacing: Option<Rc<RefCell<AdaptivePacing>>>,
+    /// Fails requests fast while Steam is down, if set.
+    pub(crate) circuit_breaker: Option<Rc<RefCell<CircuitBreaker>>>,
+    /// Authenticates Steam Web API calls instead of the API key, if set.
+    pub(crate) access_token: Option<String>,
+    /// Skips asking Steam whether the partner recently enabled Steam Guard, when creating an offer fails.
+    pub(crate) skip_guard_check: bool,
+    /// Looks for an identical outstanding offer before creating a new one, if set.
+    pub(crate) dedupe_offers: bool,
+    /// Leaves out received offers with items of other apps when polling, if set.
+    pub(crate) supported_apps: Option<SupportedApps>,
+    /// Waits for the delay between requests since the latest one before every request, if set.
+    ///
+    /// Set by `TradeManagerRegistry`, whose managers share `last_request_at`, so that requests of every account are
+    /// rate limited together.
+    pub(crate) throttle: bool,
+    /// Source of the current time for the time dependent methods.
+    #[cfg(feature = "time")]
+    pub(crate) clock: Rc<dyn time::Clock>,
+}
+
+impl Default for ManagerConfig {
+    fn default() -> Self {
+        Self {
+            api_client: Rc::new(RefCell::new(None)),
+            mobile_guard_verified: Rc::new(Cell::new(false)),
+            last_request_at: Rc::new(Cell::new(None)),
+            #[cfg(feature = "persona")]
+            persona_cache: Rc::new(RefCell::new(HashMap::new())),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            poll_interval: Duration::from_millis(STANDARD_DELAY),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            proxy: None,
+            pacing: None,
+            circuit_breaker: None,
+            access_token: None,
+            skip_guard_check: false,
+            dedupe_offers: false,
+            supported_apps: None,
+            throttle: false,
+            #[cfg(feature = "time")]
+            clock: Rc::new(time::SystemClock),
+        }
+    }
+}
+
+/// Implements the `with_*` builders of the settings of `ManagerConfig` on a manager with a `config` field.
+///
+/// Types are named as imported in the modules of both managers.
+macro_rules! impl_config_builders {
+    ($manager:ty $(, $lt:lifetime)?) => {
+        impl$(<$lt>)? $manager {
+            /// Sends `user_agent` as the `User-Agent` header of every request, instead of `DEFAULT_USER_AGENT`.
+            ///
+            /// Fails with `TradeError::InvalidUserAgent` if `user_agent` is not a valid header value.
+            pub fn with_user_agent<T: Into<String>>(mut self, user_agent: T) -> Result<Self, TradeError> {
+                let user_agent = user_agent.into();
+                validate_user_agent(&user_agent)?;
+                self.config.user_agent = user_agent;
+                Ok(self)
+            }
+
+            /// Waits `poll_interval` between checks when polling Steam for the state of an offer.
+            pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+                self.config.poll_interval = poll_interval;
+                self
+            }
+
+            /// Fails with `TradeError::ResponseTooLarge` instead of reading responses larger than `max_response_bytes`.
+            ///
+            /// Defaults to `DEFAULT_MAX_RESPONSE_BYTES`.
+            pub fn with_max_response_bytes(mut self, max_response_bytes: usize) -> Self {
+                self.config.max_response_bytes = max_response_bytes;
+                self
+            }
+
+            /// Sends Steam Web API requests through the proxy at `proxy_url`, e.g. "socks5://127.0.0.1:1080".
+            ///
+            /// Steam Community requests are sent by the authenticator, so it must be given the same proxy with
+            /// `SteamAuthenticator::with_proxy` before login, to route the whole account through it.
+            pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self, TradeError> {
+                validate_proxy(proxy_url)?;
+                self.config.proxy = Some(proxy_url.to_string());
+                Ok(self)
+            }
+
+            /// Waits between requests of batch operations as decided by `pacing`, instead of a fixed second.
+            ///
+            /// The delay grows while Steam answers slowly, and eases off back to its minimum once it is fast again.
+            /// Clones of an `OwnedSteamTradeManager` share the same pacing, so latencies observed by any of them count.
+            pub fn with_adaptive_pacing(mut self, pacing: AdaptivePacing) -> Self {
+                self.config.pacing = Some(Rc::new(RefCell::new(pacing)));
+                self
+            }
+
+            /// Stops sending trade offer requests and Steam Web API calls after repeated failures that hint at a Steam
+            /// outage, failing fast with `TradeError::CircuitOpen` instead, until `circuit_breaker` lets a request
+            /// probe whether Steam recovered.
+            ///
+            /// Clones of an `OwnedSteamTradeManager` share the same circuit, so failures seen by any of them count.
+            pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+                self.config.circuit_breaker = Some(Rc::new(RefCell::new(circuit_breaker)));
+                self
+            }
+
+            /// Authenticates Steam Web API calls with `access_token`, the access token of a logged in session,
+            /// instead of the API key cached by the authenticator.
+            ///
+            /// Steam is deprecating Web API keys, so accounts without one can still use the Web API methods of the
+            /// manager.
+            pub fn with_access_token<T: Into<String>>(mut self, access_token: T) -> Self {
+                self.config.access_token = Some(access_token.into());
+                self
+            }
+
+            /// When creating an offer fails, Steam is asked again whether the partner recently enabled Steam Guard,
+            /// to return `OfferError::SteamGuardRecentlyEnabled`. Set `skip` to avoid that extra request, e.g. with
+            /// partners already known to be able to trade.
+            pub fn with_skip_guard_check(mut self, skip: bool) -> Self {
+                self.config.skip_guard_check = skip;
+                self
+            }
+
+            /// Makes `create_offer` look for an outstanding offer identical to the one being created, to the same
+            /// partner, and return it instead of creating a duplicate.
+            ///
+            /// Costs an extra request to fetch the active offers on every create, so it is disabled by default.
+            pub fn with_dedupe_offers(mut self, dedupe: bool) -> Self {
+                self.config.dedupe_offers = dedupe;
+                self
+            }
+
+            /// Makes `poll_received_offers` leave out offers with items of apps outside of `supported_apps`,
+            /// declining them or not as decided by its `UnsupportedAppPolicy`.
+            pub fn with_supported_apps(mut self, supported_apps: SupportedApps) -> Self {
+                self.config.supported_apps = Some(supported_apps);
+                self
+            }
+
+            /// Reads the current time from `clock`, instead of the system clock, in the time dependent methods.
+            ///
+            /// Useful to test them deterministically with a `time::MockClock`.
+            #[cfg(feature = "time")]
+            pub fn with_clock(mut self, clock: Rc<dyn time::Clock>) -> Self {
+                self.config.clock = clock;
+                self
+            }
+        }
+    };
+}
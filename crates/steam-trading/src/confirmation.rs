@@ -0,0 +1,153 @@
+//! Pluggable backend used to fetch and process mobile confirmations.
+//!
+//! By default, `SteamTradeManager` confirms offers with the `SteamAuthenticator` it was created with. If confirmations
+//! are handled elsewhere, e.g. a remote service that holds the identity secrets, implement `ConfirmationProvider` and
+//! hand it to the manager with `SteamTradeManager::with_confirmation_provider`.
+//!
+//! If only the identity secret is at hand, `SteamTradeManager::confirm_with_key` confirms a single offer by generating
+//! the confirmation keys locally.
+
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use regex::Regex;
+use steam_language_gen::generated::enums::EResult;
+use steam_mobile::client::SteamAuthenticator;
+use steam_mobile::errors::AuthError;
+use steam_mobile::{ConfirmationMethod, Confirmations};
+use steam_totp::{generate_confirmation_key, Secret, Time};
+
+use crate::{ConfirmationError, TradeError};
+
+pub(crate) const MOBILECONF_BASE: &str = "https://steamcommunity.com/mobileconf/";
+
+lazy_static! {
+    static ref CONFIRMATION_ENTRY_REGEX: Regex =
+        Regex::new(r#"data-confid="(?P<id>\d+)"\s+data-key="(?P<key>\d+)"[^>]*data-creator="(?P<creator>\d+)""#)
+            .unwrap();
+}
+
+#[async_trait(?Send)]
+pub trait ConfirmationProvider: Debug {
+    /// Fetch every pending confirmation.
+    ///
+    /// Should return an empty `Confirmations` if there is none.
+    async fn fetch(&self) -> Result<Confirmations, TradeError>;
+
+    /// Accept or deny `confirmations`.
+    async fn process(&self, method: ConfirmationMethod, confirmations: Confirmations) -> Result<(), TradeError>;
+}
+
+#[async_trait(?Send)]
+impl ConfirmationProvider for SteamAuthenticator {
+    async fn fetch(&self) -> Result<Confirmations, TradeError> {
+        Ok(self.fetch_confirmations().await?.unwrap_or_default())
+    }
+
+    async fn process(&self, method: ConfirmationMethod, confirmations: Confirmations) -> Result<(), TradeError> {
+        self.process_confirmations(method, confirmations)
+            .await
+            .map_err(|e| match e {
+                AuthError::ConfirmationRejected(eresult) => ConfirmationError::Rejected { eresult }.into(),
+                e => e.into(),
+            })
+    }
+}
+
+/// Checks the response of Steam to a confirmation operation.
+///
+/// Steam may answer with an offer id, and still fail its confirmation right away with an `eresult`.
+pub(crate) fn confirmation_response_result(response: &str) -> Result<(), ConfirmationError> {
+    let response = serde_json::from_str::<serde_json::Value>(response).map_err(|_| ConfirmationError::Refused)?;
+    let eresult = response["eresult"]
+        .as_i64()
+        .and_then(|eresult| serde_json::from_value::<EResult>(eresult.into()).ok());
+
+    match eresult {
+        Some(eresult) if eresult != EResult::OK => Err(ConfirmationError::Rejected { eresult }),
+        _ if response["success"] == true => Ok(()),
+        _ => Err(ConfirmationError::Refused),
+    }
+}
+
+/// Query string of a request to the mobile confirmations page, signed with a key generated from `identity_secret`.
+///
+/// `tag` is what the key is for, e.g. `conf` to list confirmations and `allow` to accept one.
+pub(crate) fn confirmation_query(
+    identity_secret: &Secret,
+    time: Time,
+    steamid: u64,
+    tag: &str,
+) -> Result<String, TradeError> {
+    let key = generate_confirmation_key(identity_secret.clone(), time, Some(tag))
+        .map_err(|e| TradeError::PayloadError(format!("Failed to generate the confirmation key: {}", e)))?;
+
+    Ok(format!(
+        "p={}&a={}&k={}&t={}&m=android&tag={}",
+        steam_totp::get_device_id(&steamid.to_string()),
+        steamid,
+        encode_key(&key),
+        time,
+        tag
+    ))
+}
+
+/// Keys are base64, so they need to be escaped to be sent on the query string.
+fn encode_key(key: &str) -> String {
+    key.replace('+', "%2B").replace('/', "%2F").replace('=', "%3D")
+}
+
+/// Finds the confirmation id and key of `tradeoffer_id` on the mobile confirmations page.
+pub(crate) fn find_offer_confirmation(document: &str, tradeoffer_id: i64) -> Option<(String, String)> {
+    let tradeoffer_id = tradeoffer_id.to_string();
+
+    CONFIRMATION_ENTRY_REGEX
+        .captures_iter(document)
+        .find(|captures| captures["creator"] == *tradeoffer_id)
+        .map(|captures| (captures["id"].to_string(), captures["key"].to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offer_confirmation_in_document() {
+        let document = r#"
+<div class="mobileconf_list_entry" id="conf9870452108" data-confid="9870452108" data-key="16433489270434437457" data-type="2" data-creator="4278637554" data-cancel="Cancel" data-accept="Send Offer">
+</div>
+<div class="mobileconf_list_entry" id="conf9870452109" data-confid="9870452109" data-key="1290184223124409611" data-type="2" data-creator="4278637801" data-cancel="Cancel" data-accept="Accept">
+</div>"#;
+
+        assert_eq!(
+            find_offer_confirmation(document, 4278637801),
+            Some(("9870452109".to_string(), "1290184223124409611".to_string()))
+        );
+        assert_eq!(find_offer_confirmation(document, 1), None);
+    }
+
+    #[test]
+    fn confirmation_response() {
+        assert!(confirmation_response_result(r#"{"success":true}"#).is_ok());
+        assert!(matches!(
+            confirmation_response_result(r#"{"success":false,"eresult":16}"#),
+            Err(ConfirmationError::Rejected {
+                eresult: EResult::Timeout
+            })
+        ));
+        assert!(matches!(
+            confirmation_response_result(r#"{"success":true,"eresult":2}"#),
+            Err(ConfirmationError::Rejected { eresult: EResult::Fail })
+        ));
+        assert!(matches!(
+            confirmation_response_result(r#"{"success":false}"#),
+            Err(ConfirmationError::Refused)
+        ));
+    }
+
+    #[test]
+    fn confirmation_key_escaped() {
+        assert_eq!(encode_key("a+b/c="), "a%2Bb%2Fc%3D");
+    }
+}
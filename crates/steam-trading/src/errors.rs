@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use steam_mobile::errors::AuthError;
 use steam_mobile::HttpError;
 use steam_language_gen::generated::enums::EResult;
@@ -10,6 +12,35 @@ pub enum TradeError {
     #[error("`{0}`")]
     PayloadError(String),
 
+    /// Steam answered with its login page, so the session is dead and a new login is needed.
+    #[error("The session has expired. Login again before retrying.")]
+    SessionExpired,
+
+    /// Steam did not reach the expected state in time.
+    #[error("Timed out waiting for Steam.")]
+    Timeout,
+
+    /// Steam failed repeatedly, so requests are not sent until `retry_after` passes. See `CircuitBreaker`.
+    #[error("Steam looks down, requests are paused for `{retry_after:?}`.")]
+    CircuitOpen { retry_after: Duration },
+
+    /// Steam answered with a body larger than the manager allows to read, in bytes.
+    #[error("The response was larger than the limit of `{0}` bytes.")]
+    ResponseTooLarge(usize),
+
+    /// The proxy url could not be parsed, or the HTTP client could not be built with it.
+    #[error("Invalid proxy url `{0}`: {1}")]
+    InvalidProxy(String, String),
+
+    /// The User-Agent could not be sent as a header value, e.g. because it has a line break.
+    #[error("Invalid User-Agent `{0}`.")]
+    InvalidUserAgent(String),
+
+    /// Our own account is holding every trade, usually because it recently added or moved its mobile authenticator.
+    /// Trading should be paused until `until`, a unix epoch.
+    #[error("Trades of this account are held in escrow until `{until}`.")]
+    OwnAccountEscrowHold { until: i64 },
+
     #[error(transparent)]
     ConfirmationError(#[from] ConfirmationError),
 
@@ -19,6 +50,9 @@ pub enum TradeError {
     #[error(transparent)]
     TradeValidationError(#[from] OfferValidationError),
 
+    #[error(transparent)]
+    TradelinkError(#[from] TradelinkError),
+
     #[error(transparent)]
     SteamAPIError(#[from] SteamAPIError),
 
@@ -36,6 +70,14 @@ pub enum TradeError {
 pub enum TradelinkError {
     #[error("The Tradeoffer URL was not valid.")]
     Invalid,
+
+    /// The partner is not an individual account on the public universe, such as a group.
+    #[error("The Tradeoffer URL partner id is not a valid individual account.")]
+    InvalidPartnerId,
+
+    /// The partner regenerated their trade token, and the tradelink should be discarded.
+    #[error("The Tradeoffer URL token is no longer valid. The partner must provide a new one.")]
+    TokenExpired,
 }
 
 #[derive(Error, Debug, PartialEq)]
@@ -46,6 +88,26 @@ pub enum OfferValidationError {
 
     #[error("`{0}`")]
     InvalidTrade(String),
+
+    #[error("There can't be a trade offer with no items being traded.")]
+    EmptyOffer,
+
+    /// One side of the offer has more items than the max allowed, in `TRADE_MAX_ITEMS`.
+    #[error("Maximum number of items on each side is `{0}`.")]
+    TooManyItems(u8),
+
+    #[error("There are assets added by classid. Resolve them with `TradeOffer::resolve_fungible` first.")]
+    UnresolvedFungible,
+
+    #[error("Not enough tradable items of classid `{classid}` in the inventory. Missing: `{missing}`")]
+    NotEnoughItems { classid: i64, missing: i64 },
+
+    /// Steam refuses offers with the same asset twice.
+    #[error("Asset `{0}` was added more than once.")]
+    DuplicateAsset(String),
+
+    #[error("Invalid asset: `{0}`")]
+    InvalidAsset(String),
 }
 
 #[derive(Error, Debug, PartialEq)]
@@ -75,6 +137,43 @@ pub enum OfferError {
     )]
     SteamGuardRecentlyEnabled,
 
+    #[error("There was an error sending your trade offer. This is usually transient, please try again later.")]
+    TransientSendFailure,
+
+    #[error("This account is limited, and can't trade until it spends at least $5 on Steam.")]
+    OwnAccountLimited,
+
+    #[error("The partner account is limited, and can't trade until it spends at least $5 on Steam.")]
+    PartnerLimitedAccount,
+
+    /// The partner was trade banned after sending the offer, so it can never be accepted. Decline it instead.
+    #[error("The partner is trade banned, so this trade offer can't be accepted.")]
+    PartnerTradeBanned,
+
+    #[error("This trade offer was changed since it was inspected, and was not accepted.")]
+    OfferChanged,
+
+    /// An asset of the offer was traded away or consumed after the offer was built. Resolve the inventory again and
+    /// retry.
+    #[error("One or more items of this trade offer are no longer available.")]
+    ItemsUnavailable,
+
+    /// Steam refused to act on the offer for this account or partner, e.g. because the partner inventory is private.
+    #[error("Steam denied access to this trade offer. The partner may not be able to trade, or is private.")]
+    AccessDenied,
+
+    /// A Steam limit was hit, such as the maximum number of ongoing offers, or a full inventory on either side.
+    #[error("A trade limit was exceeded, such as too many ongoing offers or a full inventory.")]
+    LimitExceeded,
+
+    /// The offer was accepted, but its items are held in escrow until `escrow_end`, a unix epoch.
+    #[error("The trade offer was accepted, but its items are held in escrow until `{escrow_end}`.")]
+    SettlementPending { escrow_end: i64 },
+
+    /// The offer was accepted, but Steam placed it in escrow, holding its items for `days`.
+    #[error("The trade offer was accepted, but Steam unexpectedly placed it in escrow for `{days}` days.")]
+    UnexpectedEscrow { days: u32 },
+
     #[error("General Failure: `{0}`")]
     GeneralFailure(String),
 }
@@ -85,6 +184,91 @@ pub enum ConfirmationError {
     NotFound,
     #[error("Could not find the requested confirmation, but offer was created. Trade offer id: `{0}`")]
     NotFoundButTradeCreated(i64),
+    #[error(
+        "This account does not have the Steam Guard Mobile Authenticator enabled, so offers can't be confirmed. \
+         Add an authenticator to the account first."
+    )]
+    MobileGuardNotEnabled,
+    /// The account uses email Steam Guard, so the offer must be confirmed through the email Steam sent.
+    #[error(
+        "This offer must be confirmed by email, it can't be confirmed with the mobile authenticator. Trade offer id: \
+         `{0}`"
+    )]
+    NeedsEmailConfirmation(i64),
+    #[error("Steam refused the confirmation. The identity secret may not belong to this account.")]
+    Refused,
+    /// Steam listed the confirmation, but failed it right away, so the offer will not go through.
+    #[error("Steam rejected the confirmation with `{eresult:?}`. The trade offer will not go through.")]
+    Rejected { eresult: EResult },
+}
+
+impl TradeError {
+    /// True if the same operation may succeed when tried again later, e.g. after a rate limit, a timeout or a Steam
+    /// outage. False if retrying won't help, e.g. with an invalid tradelink, a limited account or an invalid offer.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            TradeError::Timeout | TradeError::CircuitOpen { .. } => true,
+            TradeError::PayloadError(_)
+            | TradeError::SessionExpired
+            | TradeError::ResponseTooLarge(_)
+            | TradeError::InvalidProxy(..)
+            | TradeError::InvalidUserAgent(_)
+            | TradeError::OwnAccountEscrowHold { .. }
+            | TradeError::TradeValidationError(_)
+            | TradeError::TradelinkError(_) => false,
+            TradeError::ConfirmationError(e) => e.is_retryable(),
+            TradeError::TradeOfferError(e) => e.is_retryable(),
+            TradeError::SteamAPIError(e) => match e {
+                SteamAPIError::EResult(eresult, _) => is_retryable_eresult(*eresult),
+                SteamAPIError::SteamHttpError(status) => status
+                    .parse::<u16>()
+                    .map_or(false, |status| status == 429 || status >= 500),
+                SteamAPIError::HttpError(e) => is_retryable_http(e),
+                SteamAPIError::ErrorMessage(_) => false,
+            },
+            TradeError::HttpError(e) => is_retryable_http(e),
+            TradeError::AuthError(AuthError::HttpError(e)) => is_retryable_http(e),
+            TradeError::AuthError(_) => false,
+        }
+    }
+}
+
+impl OfferError {
+    /// True if acting on the offer again later may succeed. See `TradeError::is_retryable`.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            OfferError::TransientSendFailure | OfferError::LimitExceeded | OfferError::GeneralFailure(_)
+        )
+    }
+}
+
+impl ConfirmationError {
+    /// True if confirming again later may succeed, such as when the confirmation did not show up yet. See
+    /// `TradeError::is_retryable`.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ConfirmationError::NotFound)
+    }
+}
+
+fn is_retryable_eresult(eresult: EResult) -> bool {
+    matches!(
+        eresult,
+        EResult::Busy
+            | EResult::Timeout
+            | EResult::ServiceUnavailable
+            | EResult::LimitExceeded
+            | EResult::RateLimitExceeded
+            | EResult::TryAnotherCM
+    )
+}
+
+fn is_retryable_http(error: &HttpError) -> bool {
+    error.is_timeout()
+        || error.is_connect()
+        || error
+            .status()
+            .map_or(false, |status| status.as_u16() == 429 || status.is_server_error())
 }
 
 pub(crate) fn tradeoffer_error_from_eresult(eresult: EResult) -> OfferError {
@@ -92,6 +276,9 @@ pub(crate) fn tradeoffer_error_from_eresult(eresult: EResult) -> OfferError {
         EResult::Revoked => OfferError::Revoked,
         EResult::InvalidState => OfferError::InvalidState,
         EResult::NoMatch => OfferError::NoMatch,
+        EResult::AccessDenied => OfferError::AccessDenied,
+        EResult::LimitExceeded => OfferError::LimitExceeded,
+        EResult::Busy | EResult::Timeout | EResult::ServiceUnavailable => OfferError::TransientSendFailure,
         e => OfferError::GeneralFailure(format!(
             "{}{}",
             "Please check: https://steamerrors.com/",
@@ -100,19 +287,84 @@ pub(crate) fn tradeoffer_error_from_eresult(eresult: EResult) -> OfferError {
     }
 }
 
+/// Steam sends this when it fails to create the offer for no particular reason, usually when its servers are unstable.
+/// Trying again later generally works.
+const TRANSIENT_SEND_FAILURE_MESSAGE: &str = "There was an error sending your trade offer";
+
+/// Steam sends these when either side of the offer is a limited account, that never spent money on Steam.
+const OWN_ACCOUNT_LIMITED_MESSAGE: &str = "your account is limited";
+const PARTNER_LIMITED_MESSAGE: &str = "their account is limited";
+
+/// Steam sends these when accepting an offer from a partner that was trade banned after sending it.
+const PARTNER_TRADE_BANNED_MESSAGES: &[&str] = &["they have a trade ban", "has a trade ban"];
+
+/// Steam sends this when an asset of the offer left the inventory it was requested from, such as by being traded away.
+const ITEMS_UNAVAILABLE_MESSAGE: &str = "no longer available";
+
 pub(crate) fn error_from_strmessage(message: &str) -> Option<OfferError> {
-    let index_start = message.find(|c: char| c == '(')?;
-    let index_end = message.find(|c: char| c == ')')?;
+    let lowercase_message = message.to_lowercase();
+    if lowercase_message.contains(OWN_ACCOUNT_LIMITED_MESSAGE) {
+        return Some(OfferError::OwnAccountLimited);
+    }
+    if lowercase_message.contains(PARTNER_LIMITED_MESSAGE) {
+        return Some(OfferError::PartnerLimitedAccount);
+    }
+    if PARTNER_TRADE_BANNED_MESSAGES
+        .iter()
+        .any(|banned_message| lowercase_message.contains(banned_message))
+    {
+        return Some(OfferError::PartnerTradeBanned);
+    }
+    if lowercase_message.contains(ITEMS_UNAVAILABLE_MESSAGE) {
+        return Some(OfferError::ItemsUnavailable);
+    }
+
+    let error = eresult_from_strmessage(message).map(tradeoffer_error_from_eresult);
+
+    match error {
+        Some(OfferError::GeneralFailure(_)) | None if message.contains(TRANSIENT_SEND_FAILURE_MESSAGE) => {
+            Some(OfferError::TransientSendFailure)
+        }
+        error => error,
+    }
+}
+
+/// Steam sends this when the partner tradelink token was regenerated, while creating an offer.
+const TOKEN_EXPIRED_MESSAGE: &str = "Trade URL is no longer valid";
+
+pub(crate) fn tradelink_error_from_strmessage(message: &str) -> Option<TradelinkError> {
+    if message.contains(TOKEN_EXPIRED_MESSAGE) {
+        return Some(TradelinkError::TokenExpired);
+    }
+    None
+}
+
+/// Steam sends these when the session of this account is no longer valid, while acting on an offer.
+const SESSION_EXPIRED_MESSAGES: &[&str] = &["must be logged in", "not logged in"];
+
+/// True if `message` tells that the session of this account is no longer valid, as opposed to a problem with the
+/// partner's tradelink.
+pub(crate) fn is_session_error_strmessage(message: &str) -> bool {
+    let lowercase_message = message.to_lowercase();
+
+    SESSION_EXPIRED_MESSAGES
+        .iter()
+        .any(|session_message| lowercase_message.contains(session_message))
+        || eresult_from_strmessage(message) == Some(EResult::NotLoggedOn)
+}
 
-    let number = message
-        .chars()
-        .skip(index_start + 1)
-        .take(index_end - index_start - 1)
-        .collect::<String>();
+/// Recovers the EResult between parenthesis at the end of a Steam message. E.g: "Something went wrong (26)".
+///
+/// Only the trailing code is considered, so parenthesis earlier in the message are ignored.
+fn eresult_from_strmessage(message: &str) -> Option<EResult> {
+    let message = message.trim_end().strip_suffix(')')?;
+    let number = &message[message.rfind('(')? + 1..];
 
-    serde_json::from_str::<EResult>(&*number)
-        .map(tradeoffer_error_from_eresult)
+    number
+        .trim()
+        .parse::<i32>()
         .ok()
+        .and_then(|number| serde_json::from_value::<EResult>(number.into()).ok())
 }
 
 #[cfg(test)]
@@ -124,4 +376,143 @@ mod tests {
         let error_message = "Something went wrong (26)";
         assert_eq!(error_from_strmessage(error_message).unwrap(), OfferError::Revoked)
     }
+
+    #[test]
+    fn error_strmessage_trailing_code() {
+        let error_message = "You have sent too many trade offers (limit reached), please try again later. (25)";
+        assert_eq!(error_from_strmessage(error_message).unwrap(), OfferError::LimitExceeded);
+
+        let error_message = "There was an error accepting this trade offer.  Please try again later. (11)";
+        assert_eq!(error_from_strmessage(error_message).unwrap(), OfferError::InvalidState);
+
+        let error_message = "You are not allowed to send trade offers to Basinga. (15)";
+        assert_eq!(error_from_strmessage(error_message).unwrap(), OfferError::AccessDenied);
+
+        let error_message = "The trade offer could not be sent (because of reasons) (16) ";
+        assert_eq!(
+            error_from_strmessage(error_message).unwrap(),
+            OfferError::TransientSendFailure
+        );
+
+        assert_eq!(error_from_strmessage("Something went wrong (26) again"), None);
+        assert_eq!(error_from_strmessage("Something went wrong (basinga)"), None);
+    }
+
+    #[test]
+    fn retryable_errors() {
+        let retryable = vec![
+            TradeError::Timeout,
+            TradeError::CircuitOpen {
+                retry_after: Duration::from_secs(30),
+            },
+            OfferError::TransientSendFailure.into(),
+            OfferError::LimitExceeded.into(),
+            OfferError::GeneralFailure("Steam Response: <html>".to_string()).into(),
+            ConfirmationError::NotFound.into(),
+            SteamAPIError::EResult(EResult::RateLimitExceeded, "Value: 84".to_string()).into(),
+            SteamAPIError::EResult(EResult::ServiceUnavailable, "Value: 20".to_string()).into(),
+            SteamAPIError::SteamHttpError("429".to_string()).into(),
+            SteamAPIError::SteamHttpError("503".to_string()).into(),
+        ];
+        for error in retryable {
+            assert!(error.is_retryable(), "{:?}", error);
+        }
+
+        let permanent = vec![
+            TradeError::PayloadError("basinga".to_string()),
+            TradeError::SessionExpired,
+            TradeError::ResponseTooLarge(1024),
+            TradeError::InvalidProxy("basinga".to_string(), "invalid".to_string()),
+            TradeError::InvalidUserAgent("basinga\n".to_string()),
+            TradeError::OwnAccountEscrowHold { until: 1605295702 },
+            TradelinkError::TokenExpired.into(),
+            TradelinkError::Invalid.into(),
+            OfferValidationError::EmptyOffer.into(),
+            OfferError::OwnAccountLimited.into(),
+            OfferError::PartnerLimitedAccount.into(),
+            OfferError::PartnerTradeBanned.into(),
+            OfferError::SteamGuardRecentlyEnabled.into(),
+            OfferError::ItemsUnavailable.into(),
+            OfferError::InvalidState.into(),
+            OfferError::NoMatch.into(),
+            OfferError::Revoked.into(),
+            OfferError::AccessDenied.into(),
+            OfferError::OfferChanged.into(),
+            OfferError::SettlementPending { escrow_end: 1605295702 }.into(),
+            OfferError::UnexpectedEscrow { days: 15 }.into(),
+            ConfirmationError::NotFoundButTradeCreated(4278637554).into(),
+            ConfirmationError::MobileGuardNotEnabled.into(),
+            ConfirmationError::NeedsEmailConfirmation(4278637554).into(),
+            ConfirmationError::Refused.into(),
+            ConfirmationError::Rejected { eresult: EResult::Fail }.into(),
+            SteamAPIError::EResult(EResult::AccessDenied, "Value: 15".to_string()).into(),
+            SteamAPIError::ErrorMessage("basinga".to_string()).into(),
+            SteamAPIError::SteamHttpError("403".to_string()).into(),
+        ];
+        for error in permanent {
+            assert!(!error.is_retryable(), "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn error_strmessage_session_expired() {
+        assert!(is_session_error_strmessage(
+            "You must be logged in to perform this action."
+        ));
+        assert!(is_session_error_strmessage("Something went wrong (21)"));
+        assert!(!is_session_error_strmessage(
+            "This Trade URL is no longer valid for sending a trade offer to Basinga. They must provide a new URL. (15)"
+        ));
+    }
+
+    #[test]
+    fn error_strmessage_token_expired() {
+        let error_message = "This Trade URL is no longer valid for sending a trade offer to Basinga. They must provide \
+                             a new URL. (15)";
+        assert_eq!(
+            tradelink_error_from_strmessage(error_message),
+            Some(TradelinkError::TokenExpired)
+        );
+        assert_eq!(tradelink_error_from_strmessage("Something went wrong (26)"), None);
+    }
+
+    #[test]
+    fn error_strmessage_limited_account() {
+        let error_message = "You cannot trade with Basinga because your account is limited. (15)";
+        assert_eq!(
+            error_from_strmessage(error_message).unwrap(),
+            OfferError::OwnAccountLimited
+        );
+
+        let error_message = "You cannot trade with Basinga because their account is limited. (15)";
+        assert_eq!(
+            error_from_strmessage(error_message).unwrap(),
+            OfferError::PartnerLimitedAccount
+        );
+    }
+
+    #[test]
+    fn error_strmessage_items_unavailable() {
+        let error_message = "There was an error sending your trade offer.  Please try again later.<br><br>One or more \
+                             items in this trade offer are no longer available. (26)";
+        assert_eq!(
+            error_from_strmessage(error_message).unwrap(),
+            OfferError::ItemsUnavailable
+        );
+    }
+
+    #[test]
+    fn error_strmessage_transient_send_failure() {
+        let error_message = "There was an error sending your trade offer.  Please try again later. (16)";
+        assert_eq!(
+            error_from_strmessage(error_message).unwrap(),
+            OfferError::TransientSendFailure
+        );
+
+        let error_message = "There was an error sending your trade offer.  Please try again later.";
+        assert_eq!(
+            error_from_strmessage(error_message).unwrap(),
+            OfferError::TransientSendFailure
+        );
+    }
 }
@@ -0,0 +1,173 @@
+//! Writes completed trades in formats suited for bookkeeping, such as spreadsheets.
+
+use std::io::{self, Write};
+
+use serde_json::json;
+use tappet::response_types::{ETradeStatus, TradeHistory_Trade, TradeHistory_TradedAsset};
+
+const CSV_HEADER: &str = "tradeid,partner,time,items_given,items_received";
+
+/// Format completed trades are exported in.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ExportFormat {
+    /// One trade per line, after a header. Items are listed as `appid:contextid:assetid:amount`, separated by spaces.
+    Csv,
+    /// One JSON object per line.
+    JsonLines,
+}
+
+/// Writes what goes before the trades into `writer`, such as the CSV header. Written once, even if the trades are
+/// written in several calls to `write_trades`.
+pub(crate) fn write_header<W: Write>(mut writer: W, format: ExportFormat) -> io::Result<()> {
+    match format {
+        ExportFormat::Csv => writeln!(writer, "{}", CSV_HEADER),
+        ExportFormat::JsonLines => Ok(()),
+    }
+}
+
+/// Writes every completed trade of `trades` into `writer`, as `format`. Trades that did not complete are skipped.
+pub(crate) fn write_trades<W: Write>(
+    mut writer: W,
+    trades: &[TradeHistory_Trade],
+    format: ExportFormat,
+) -> io::Result<()> {
+    for trade in trades.iter().filter(|trade| trade.status == ETradeStatus::Complete) {
+        match format {
+            ExportFormat::Csv => writeln!(
+                writer,
+                "{},{},{},{},{}",
+                trade.tradeid,
+                trade.steamid_other,
+                trade.time_init,
+                csv_items(&trade.assets_given),
+                csv_items(&trade.assets_received)
+            )?,
+            ExportFormat::JsonLines => {
+                let line = json!({
+                    "tradeid": trade.tradeid.to_string(),
+                    "partner": trade.steamid_other.to_string(),
+                    "time": trade.time_init,
+                    "items_given": json_items(&trade.assets_given),
+                    "items_received": json_items(&trade.assets_received),
+                });
+                writeln!(writer, "{}", line)?
+            }
+        }
+    }
+
+    writer.flush()
+}
+
+fn csv_items(assets: &Option<Vec<TradeHistory_TradedAsset>>) -> String {
+    assets
+        .iter()
+        .flatten()
+        .map(|asset| format!("{}:{}:{}:{}", asset.appid, asset.contextid, asset.assetid, asset.amount))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn json_items(assets: &Option<Vec<TradeHistory_TradedAsset>>) -> serde_json::Value {
+    assets
+        .iter()
+        .flatten()
+        .map(|asset| {
+            json!({
+                "appid": asset.appid,
+                "contextid": asset.contextid.to_string(),
+                "assetid": asset.assetid.to_string(),
+                "new_assetid": asset.new_assetid.to_string(),
+                "amount": asset.amount,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use tappet::response_types::GetTradeHistoryResponse;
+
+    use super::*;
+
+    fn sample_trades() -> Vec<TradeHistory_Trade> {
+        let response = r#"{
+  "response": {
+    "more": false,
+    "trades": [
+      {
+        "tradeid": "3622543526924228084",
+        "steamid_other": "76561198040191316",
+        "time_init": 1603998438,
+        "status": 3,
+        "assets_given": [
+          {
+            "appid": 730,
+            "contextid": "2",
+            "assetid": "15319724006",
+            "amount": "1",
+            "classid": "3035569977",
+            "instanceid": "302028390",
+            "new_assetid": "19793871926",
+            "new_contextid": "2"
+          }
+        ],
+        "assets_received": [
+          {
+            "appid": 440,
+            "contextid": "2",
+            "assetid": "9937692380",
+            "amount": "1",
+            "classid": "310779465",
+            "instanceid": "188530139",
+            "new_assetid": "10056732112",
+            "new_contextid": "2"
+          }
+        ]
+      },
+      {
+        "tradeid": "3151905948742966439",
+        "steamid_other": "76561198040191316",
+        "time_init": 1594190957,
+        "status": 4
+      }
+    ]
+  }
+}"#;
+        serde_json::from_str::<GetTradeHistoryResponse>(response)
+            .unwrap()
+            .response
+            .trades
+    }
+
+    #[test]
+    fn export_csv() {
+        let mut output = vec![];
+        write_header(&mut output, ExportFormat::Csv).unwrap();
+        write_trades(&mut output, &sample_trades(), ExportFormat::Csv).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec![
+                CSV_HEADER,
+                "3622543526924228084,76561198040191316,1603998438,730:2:15319724006:1,440:2:9937692380:1"
+            ]
+        );
+    }
+
+    #[test]
+    fn export_json_lines() {
+        let mut output = vec![];
+        write_header(&mut output, ExportFormat::JsonLines).unwrap();
+        write_trades(&mut output, &sample_trades(), ExportFormat::JsonLines).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 1);
+
+        let trade = serde_json::from_str::<serde_json::Value>(lines[0]).unwrap();
+        assert_eq!(trade["tradeid"], "3622543526924228084");
+        assert_eq!(trade["items_received"][0]["new_assetid"], "10056732112");
+        assert_eq!(trade["items_given"][0]["appid"], 730);
+    }
+}
@@ -0,0 +1,241 @@
+//! Helpers to search the trade history.
+//!
+//! A GetTradeHistory response body can be searched while it is being downloaded, one trade at a time, so neither the
+//! whole body nor every deserialized trade of it is held in memory at once.
+
+use serde::de::Error;
+use tappet::response_types::TradeHistory_Trade;
+
+/// Keys of the objects leading to the `trades` array, from the root of the response.
+const TRADES_PATH: [&[u8]; 2] = [b"response", b"trades"];
+
+/// Searches the trades of a GetTradeHistory response body, fed in chunks as they are downloaded.
+///
+/// Only the bytes of the trade being read are kept around, and each trade is deserialized on its own as soon as it is
+/// complete. Everything outside of the `trades` array is skipped without being parsed.
+#[derive(Debug, Default)]
+pub(crate) struct TradeScanner {
+    /// Containers the scanner is currently inside of, from the root of the response.
+    containers: Vec<Container>,
+    in_string: bool,
+    escaped: bool,
+    /// Raw bytes of the object key being read, if the current string is one.
+    key: Option<Vec<u8>>,
+    /// Raw bytes of the trade being read, if inside one.
+    trade: Option<Vec<u8>>,
+}
+
+#[derive(Debug)]
+struct Container {
+    is_object: bool,
+    /// Key of the latest member of the object.
+    key: Option<Vec<u8>>,
+    /// Whether the next string of the object is a key, instead of a value.
+    expecting_key: bool,
+}
+
+impl TradeScanner {
+    /// Scans the next `chunk` of the response body, returning the first trade completed within it that matches
+    /// `predicate`.
+    pub(crate) fn feed<F>(&mut self, chunk: &[u8], predicate: &F) -> serde_json::Result<Option<TradeHistory_Trade>>
+    where
+        F: Fn(&TradeHistory_Trade) -> bool,
+    {
+        for &byte in chunk {
+            if let Some(trade) = &mut self.trade {
+                trade.push(byte);
+            }
+
+            if self.in_string {
+                self.scan_string(byte);
+                continue;
+            }
+
+            match byte {
+                b'"' => {
+                    self.in_string = true;
+                    if let Some(container) = self.containers.last_mut().filter(|container| container.expecting_key) {
+                        container.expecting_key = false;
+                        self.key = Some(vec![]);
+                    }
+                }
+                b'{' | b'[' => {
+                    if byte == b'{' && self.at_trades() {
+                        self.trade = Some(vec![byte]);
+                    }
+                    self.containers.push(Container {
+                        is_object: byte == b'{',
+                        key: None,
+                        expecting_key: byte == b'{',
+                    });
+                }
+                b'}' | b']' => {
+                    match self.containers.pop() {
+                        Some(container) if container.is_object == (byte == b'}') => {}
+                        _ => return Err(serde_json::Error::custom("unbalanced GetTradeHistory response")),
+                    }
+
+                    if self.at_trades() {
+                        if let Some(trade) = self.trade.take() {
+                            let trade = serde_json::from_slice::<TradeHistory_Trade>(&trade)?;
+                            if predicate(&trade) {
+                                return Ok(Some(trade));
+                            }
+                        }
+                    }
+                }
+                b',' => {
+                    if let Some(container) = self.containers.last_mut().filter(|container| container.is_object) {
+                        container.expecting_key = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Checks that the whole response body was scanned, after its last chunk.
+    pub(crate) fn finish(self) -> serde_json::Result<()> {
+        if self.in_string || !self.containers.is_empty() {
+            return Err(serde_json::Error::custom("truncated GetTradeHistory response"));
+        }
+        Ok(())
+    }
+
+    fn scan_string(&mut self, byte: u8) {
+        if self.escaped {
+            self.escaped = false;
+        } else if byte == b'\\' {
+            self.escaped = true;
+        } else if byte == b'"' {
+            self.in_string = false;
+            if let Some(key) = self.key.take() {
+                if let Some(container) = self.containers.last_mut() {
+                    container.key = Some(key);
+                }
+            }
+            return;
+        }
+
+        if let Some(key) = &mut self.key {
+            key.push(byte);
+        }
+    }
+
+    /// Whether the scanner is right inside the `trades` array, where each object is a trade.
+    fn at_trades(&self) -> bool {
+        match &*self.containers {
+            [root, response, trades] => {
+                root.is_object
+                    && root.key.as_deref() == Some(TRADES_PATH[0])
+                    && response.is_object
+                    && response.key.as_deref() == Some(TRADES_PATH[1])
+                    && !trades.is_object
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Moves the trades of a GetTradeHistory page with `time_init` within `[start, end]` into `collected`.
+///
+/// Steam lists trades from newest to oldest, so returns true once a trade older than `start` is found, meaning that
+/// the following pages don't need to be fetched.
+pub(crate) fn collect_trades_between(
+    trades: Vec<TradeHistory_Trade>,
+    start: u32,
+    end: u32,
+    collected: &mut Vec<TradeHistory_Trade>,
+) -> bool {
+    let mut reached_start = false;
+
+    for trade in trades {
+        if trade.time_init < i64::from(start) {
+            reached_start = true;
+        } else if trade.time_init <= i64::from(end) {
+            collected.push(trade);
+        }
+    }
+
+    reached_start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_document() -> &'static str {
+        r#"{
+  "response": {
+    "more": false,
+    "trades": [
+      {
+        "tradeid": "3622543526924228084",
+        "steamid_other": "76561198040191316",
+        "time_init": 1603998438,
+        "status": 3,
+        "assets_given": [
+          {
+            "appid": 730,
+            "contextid": "2",
+            "assetid": "15319724006",
+            "amount": "1",
+            "classid": "3035569977",
+            "instanceid": "302028390",
+            "new_assetid": "19793871926",
+            "new_contextid": "2"
+          }
+        ]
+      },
+      {
+        "tradeid": "1640843092290105607",
+        "steamid_other": "76561197998993178",
+        "time_init": 1492806587,
+        "status": "malformed, to show that trades after the match are never deserialized"
+      }
+    ]
+  }
+}"#
+    }
+
+    fn scan_in_chunks(
+        document: &str,
+        chunk_size: usize,
+        tradeid: i64,
+    ) -> serde_json::Result<Option<TradeHistory_Trade>> {
+        let mut scanner = TradeScanner::default();
+        for chunk in document.as_bytes().chunks(chunk_size) {
+            if let Some(trade) = scanner.feed(chunk, &|trade: &TradeHistory_Trade| trade.tradeid == tradeid)? {
+                return Ok(Some(trade));
+            }
+        }
+        scanner.finish().map(|_| None)
+    }
+
+    #[test]
+    fn scan_stops_at_match() {
+        for chunk_size in 1..=sample_document().len() {
+            let trade = scan_in_chunks(sample_document(), chunk_size, 3622543526924228084)
+                .unwrap()
+                .unwrap();
+            assert_eq!(trade.time_init, 1603998438);
+        }
+    }
+
+    #[test]
+    fn scan_without_match() {
+        let not_found = scan_in_chunks(r#"{"response":{"more":false,"trades":[]}}"#, 7, 3622543526924228084).unwrap();
+        assert!(not_found.is_none());
+
+        let nested_keys = r#"{"response":{"trades":[{"tradeid":"1","steamid_other":"76561197998993178","time_init":1,"status":3,"extra":{"trades":["{[\\\"]"]}}]}}"#;
+        assert!(scan_in_chunks(nested_keys, 5, 3622543526924228084).unwrap().is_none());
+    }
+
+    #[test]
+    fn scan_truncated() {
+        let document = &sample_document()[..40];
+        assert!(scan_in_chunks(document, 16, 3622543526924228084).is_err());
+    }
+}
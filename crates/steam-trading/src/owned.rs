@@ -0,0 +1,175 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use steam_mobile::client::SteamAuthenticator;
+use steam_mobile::errors::AuthError;
+use steam_mobile::User;
+
+use crate::config::ManagerConfig;
+#[cfg(feature = "record")]
+use crate::record::{RecordSink, Replay};
+#[cfg(feature = "time")]
+use crate::time;
+use crate::{
+    validate_proxy, validate_user_agent, AdaptivePacing, CircuitBreaker, ConfirmationProvider, SteamTradeManager,
+    SupportedApps, TradeError,
+};
+
+/// Owned counterpart of `SteamTradeManager`, that can be cloned and moved into `'static` futures.
+///
+/// It is `Rc` based and `!Send`, since `SteamAuthenticator` is not `Sync`. Futures using it must run on the current
+/// thread, e.g. spawned with `spawn_local` inside a `tokio::task::LocalSet`, instead of `tokio::spawn`.
+///
+/// Every clone shares the same lazily built `SteamAPI` client, so it is only created once, along with the pacing,
+/// circuit breaker and caches of the manager.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::rc::Rc;
+///
+/// use steam_mobile::client::SteamAuthenticator;
+/// use steam_mobile::User;
+/// use steam_trading::OwnedSteamTradeManager;
+/// use tokio::task::LocalSet;
+///
+/// # async fn run() {
+/// let authenticator = SteamAuthenticator::new(User::new("username".to_string(), "password".to_string()));
+/// authenticator.login(None).await.unwrap();
+/// let owned = OwnedSteamTradeManager::new(Rc::new(authenticator));
+///
+/// let local = LocalSet::new();
+/// local
+///     .run_until(async move {
+///         let polling = owned.clone();
+///         let handle = tokio::task::spawn_local(async move { polling.manager().cancel_offer(4112828817).await });
+///         handle.await.unwrap().unwrap();
+///     })
+///     .await;
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OwnedSteamTradeManager {
+    authenticator: Rc<SteamAuthenticator>,
+    confirmation_provider: Option<Rc<dyn ConfirmationProvider>>,
+    config: ManagerConfig,
+    #[cfg(feature = "record")]
+    recorder: Option<Rc<dyn RecordSink>>,
+    #[cfg(feature = "record")]
+    replay: Option<Rc<Replay>>,
+}
+
+impl_config_builders!(OwnedSteamTradeManager);
+
+impl OwnedSteamTradeManager {
+    pub fn new(authenticator: Rc<SteamAuthenticator>) -> Self {
+        Self {
+            authenticator,
+            confirmation_provider: None,
+            config: ManagerConfig::default(),
+            #[cfg(feature = "record")]
+            recorder: None,
+            #[cfg(feature = "record")]
+            replay: None,
+        }
+    }
+
+    /// Builds a manager from the cookies of a session that was logged in elsewhere, such as by a separate
+    /// authentication service, without logging in again.
+    ///
+    /// See `SteamAuthenticator::from_cookies`. If `user` has no mobile authenticator file linked, offers can still be
+    /// confirmed with `SteamTradeManager::confirm_with_key`, or through a `ConfirmationProvider`.
+    pub fn from_cookies(
+        user: User,
+        session_id: &str,
+        steam_login_secure: &str,
+        api_key: Option<String>,
+    ) -> Result<Self, TradeError> {
+        SteamAuthenticator::from_cookies(user, session_id, steam_login_secure, api_key)
+            .map(|authenticator| Self::new(Rc::new(authenticator)))
+            .map_err(|e| AuthError::from(e).into())
+    }
+
+    /// Fetch and process confirmations through `provider`, instead of the authenticator the manager was created with.
+    pub fn with_confirmation_provider(mut self, provider: Rc<dyn ConfirmationProvider>) -> Self {
+        self.confirmation_provider = Some(provider);
+        self
+    }
+
+    /// Records every request made by managers built from this one, along with its response, into `recorder`.
+    #[cfg(feature = "record")]
+    pub fn with_recorder(mut self, recorder: Rc<dyn RecordSink>) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Answers requests with the responses of `replay`, instead of sending them to Steam. See
+    /// `SteamTradeManager::with_replay`.
+    #[cfg(feature = "record")]
+    pub fn with_replay(mut self, replay: Rc<Replay>) -> Self {
+        self.replay = Some(replay);
+        self
+    }
+
+    /// Rate limits requests together with other managers, such as the ones of a `TradeManagerRegistry`, waiting as
+    /// decided by `pacing` since the latest request of any of them, recorded in `last_request_at`.
+    pub(crate) fn with_shared_limiter(
+        mut self,
+        pacing: Rc<RefCell<AdaptivePacing>>,
+        last_request_at: Rc<Cell<Option<Instant>>>,
+    ) -> Self {
+        self.config.pacing = Some(pacing);
+        self.config.last_request_at = last_request_at;
+        self.config.throttle = true;
+        self
+    }
+
+    /// Returns a `SteamTradeManager` borrowing this owned manager, to perform trade operations.
+    ///
+    /// The returned manager has every setting of this one, and shares the `SteamAPI` client with every clone of it.
+    pub fn manager(&self) -> SteamTradeManager<'_> {
+        SteamTradeManager {
+            authenticator: &self.authenticator,
+            confirmation_provider: self.confirmation_provider.as_deref().unwrap_or(&*self.authenticator),
+            config: self.config.clone(),
+            #[cfg(feature = "record")]
+            recorder: self.recorder.as_deref(),
+            #[cfg(feature = "record")]
+            replay: self.replay.as_deref(),
+        }
+    }
+
+    pub fn authenticator(&self) -> &SteamAuthenticator {
+        &self.authenticator
+    }
+}
+
+#[cfg(all(test, feature = "record"))]
+mod tests {
+    use super::*;
+    use crate::record::RecordedExchange;
+
+    #[tokio::test]
+    async fn manager_keeps_settings() {
+        let replay = Replay::new(vec![RecordedExchange {
+            endpoint: "https://steamcommunity.com/tradeoffer/4112828817/cancel".to_string(),
+            method: "POST".to_string(),
+            body: None,
+            response: r#"{"tradeofferid":"4112828817"}"#.to_string(),
+        }]);
+        let owned = OwnedSteamTradeManager::from_cookies(
+            User::new("username".to_string(), "password".to_string()),
+            "sessionid",
+            "76561198040191316%7C%7Ctoken",
+            None,
+        )
+        .unwrap()
+        .with_poll_interval(Duration::from_millis(250))
+        .with_replay(Rc::new(replay));
+
+        let manager = owned.manager();
+        assert_eq!(manager.config.poll_interval, Duration::from_millis(250));
+        assert!(manager.cancel_offer(4112828817).await.is_ok());
+    }
+}
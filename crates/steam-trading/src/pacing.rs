@@ -0,0 +1,93 @@
+//! Adaptive delay between requests, based on how fast Steam is answering.
+//!
+//! Steam answers slowly when it is under load, or when it is about to rate limit an account. Instead of a fixed delay
+//! between requests, `AdaptivePacing` waits longer while Steam is slow, and eases back to the minimum once it is fast
+//! again.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Requests considered by the moving average of latencies.
+const DEFAULT_WINDOW: usize = 10;
+
+/// Average latency above which Steam is considered slow.
+const DEFAULT_SLOW_THRESHOLD: Duration = Duration::from_secs(2);
+
+/// Delay between requests, that grows while Steam is slow and shrinks while it is fast.
+///
+/// Enable it with `SteamTradeManager::with_adaptive_pacing`.
+#[derive(Debug, Clone)]
+pub struct AdaptivePacing {
+    min_delay: Duration,
+    max_delay: Duration,
+    slow_threshold: Duration,
+    latencies: VecDeque<Duration>,
+    delay: Duration,
+}
+
+impl AdaptivePacing {
+    /// Paces requests between `min_delay` and `max_delay`, starting at `min_delay`.
+    pub fn new(min_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            min_delay,
+            max_delay: max_delay.max(min_delay),
+            slow_threshold: DEFAULT_SLOW_THRESHOLD,
+            latencies: VecDeque::with_capacity(DEFAULT_WINDOW),
+            delay: min_delay,
+        }
+    }
+
+    /// Considers Steam slow once the average latency is above `slow_threshold`, instead of two seconds.
+    pub fn with_slow_threshold(mut self, slow_threshold: Duration) -> Self {
+        self.slow_threshold = slow_threshold;
+        self
+    }
+
+    /// Current delay between requests.
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Moving average of the latest request latencies, if any request was made yet.
+    pub fn average_latency(&self) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        Some(self.latencies.iter().sum::<Duration>() / self.latencies.len() as u32)
+    }
+
+    /// Records how long a request took, doubling the delay if Steam is slow, or easing it off by a quarter if not.
+    pub(crate) fn record(&mut self, latency: Duration) {
+        if self.latencies.len() == DEFAULT_WINDOW {
+            self.latencies.pop_front();
+        }
+        self.latencies.push_back(latency);
+
+        let is_slow = self
+            .average_latency()
+            .map_or(false, |average| average > self.slow_threshold);
+        self.delay = if is_slow {
+            (self.delay * 2).min(self.max_delay)
+        } else {
+            (self.delay * 3 / 4).max(self.min_delay)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grows_while_slow_and_eases_off() {
+        let mut pacing = AdaptivePacing::new(Duration::from_secs(1), Duration::from_secs(8));
+        assert_eq!(pacing.delay(), Duration::from_secs(1));
+
+        (0..5).for_each(|_| pacing.record(Duration::from_secs(5)));
+        assert_eq!(pacing.delay(), Duration::from_secs(8));
+
+        (0..DEFAULT_WINDOW * 2).for_each(|_| pacing.record(Duration::from_millis(100)));
+        assert_eq!(pacing.delay(), Duration::from_secs(1));
+        assert_eq!(pacing.average_latency(), Some(Duration::from_millis(100)));
+    }
+}
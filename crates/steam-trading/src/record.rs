@@ -0,0 +1,132 @@
+//! Records trade requests and their responses, and replays them back, to reproduce Steam specific bugs offline.
+//!
+//! Hand a `RecordSink` to the manager with `SteamTradeManager::with_recorder`, and every request made through it will
+//! be recorded. Later, the recorded exchanges can be fed back with `SteamTradeManager::with_replay`, and no request
+//! will reach Steam at all.
+//!
+//! Can be enabled by adding the snippet below in your Cargo.toml:
+//! ```toml
+//! steam-trading = { version = "*", features = ["record"] }
+//! ```
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// A single request sent to Steam, and the response it got back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    pub endpoint: String,
+    pub method: String,
+    /// Request body, serialized as JSON.
+    pub body: Option<String>,
+    pub response: String,
+}
+
+pub trait RecordSink: Debug {
+    fn record(&self, exchange: RecordedExchange);
+}
+
+impl RecordSink for RefCell<Vec<RecordedExchange>> {
+    fn record(&self, exchange: RecordedExchange) {
+        self.borrow_mut().push(exchange);
+    }
+}
+
+/// Writes each exchange as a line of JSON, e.g. to a file.
+#[derive(Debug)]
+pub struct JsonLinesSink<W: Write>(RefCell<W>);
+
+impl<W: Write> JsonLinesSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self(RefCell::new(writer))
+    }
+
+    pub fn into_inner(self) -> W {
+        self.0.into_inner()
+    }
+}
+
+impl<W: Write + Debug> RecordSink for JsonLinesSink<W> {
+    fn record(&self, exchange: RecordedExchange) {
+        let mut writer = self.0.borrow_mut();
+        let result = serde_json::to_writer(&mut *writer, &exchange)
+            .map_err(std::io::Error::from)
+            .and_then(|_| writeln!(writer));
+
+        if let Err(e) = result {
+            warn!("Failed to record exchange with {}: {}", exchange.endpoint, e);
+        }
+    }
+}
+
+/// Responses recorded earlier, fed back instead of reaching Steam.
+#[derive(Debug, Default)]
+pub struct Replay {
+    exchanges: RefCell<VecDeque<RecordedExchange>>,
+}
+
+impl Replay {
+    pub fn new(exchanges: Vec<RecordedExchange>) -> Self {
+        Self {
+            exchanges: RefCell::new(exchanges.into()),
+        }
+    }
+
+    /// Reads exchanges written by `JsonLinesSink`.
+    pub fn from_json_lines(json_lines: &str) -> Result<Self, serde_json::Error> {
+        json_lines
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::new)
+    }
+
+    /// Takes the oldest response recorded for `endpoint` and `method`.
+    ///
+    /// Each response is replayed only once, so repeated requests get their responses in the order they were recorded.
+    pub(crate) fn next_response(&self, endpoint: &str, method: &str) -> Option<String> {
+        let mut exchanges = self.exchanges.borrow_mut();
+        let position = exchanges
+            .iter()
+            .position(|exchange| exchange.endpoint == endpoint && exchange.method == method)?;
+
+        exchanges.remove(position).map(|exchange| exchange.response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exchange(endpoint: &str, response: &str) -> RecordedExchange {
+        RecordedExchange {
+            endpoint: endpoint.to_string(),
+            method: "GET".to_string(),
+            body: None,
+            response: response.to_string(),
+        }
+    }
+
+    #[test]
+    fn replay_in_recorded_order() {
+        let sink = JsonLinesSink::new(vec![]);
+        sink.record(exchange("https://steamcommunity.com/a", "first"));
+        sink.record(exchange("https://steamcommunity.com/b", "other"));
+        sink.record(exchange("https://steamcommunity.com/a", "second"));
+
+        let recorded = String::from_utf8(sink.into_inner()).unwrap();
+        let replay = Replay::from_json_lines(&recorded).unwrap();
+
+        let endpoint = "https://steamcommunity.com/a";
+        assert_eq!(replay.next_response(endpoint, "GET").as_deref(), Some("first"));
+        assert_eq!(replay.next_response(endpoint, "GET").as_deref(), Some("second"));
+        assert_eq!(replay.next_response(endpoint, "GET"), None);
+        assert_eq!(replay.next_response("https://steamcommunity.com/b", "POST"), None);
+    }
+}
@@ -0,0 +1,162 @@
+//! Registry of the managers of many accounts, for services that trade with several accounts at once.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use futures::{stream, StreamExt};
+use steamid_parser::SteamID;
+use tappet::response_types::TradeOffer_Trade;
+use tracing::warn;
+
+use crate::{AdaptivePacing, OwnedSteamTradeManager, TradeError, STANDARD_DELAY};
+
+/// Longest delay between requests of the default shared pacing, while Steam is slow.
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(10);
+
+/// Max accounts being polled at the same time.
+const POLL_CONCURRENCY: usize = 4;
+
+/// Owns an `OwnedSteamTradeManager` for each account, keyed by the account SteamID.
+///
+/// Every registered manager shares a single rate limiter, so requests of all accounts are spaced out together, whether
+/// they are sent by `poll_all` or through a manager returned by `for_account`. Errors are kept per account: a failure
+/// of one account never stops the others from being polled.
+#[derive(Debug)]
+pub struct TradeManagerRegistry {
+    managers: Vec<(SteamID, OwnedSteamTradeManager)>,
+    pacing: Rc<RefCell<AdaptivePacing>>,
+    last_request_at: Rc<Cell<Option<Instant>>>,
+}
+
+impl Default for TradeManagerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TradeManagerRegistry {
+    /// Creates an empty registry, pacing requests a second apart, and further apart while Steam is slow.
+    pub fn new() -> Self {
+        Self {
+            managers: vec![],
+            pacing: Rc::new(RefCell::new(AdaptivePacing::new(
+                Duration::from_millis(STANDARD_DELAY),
+                DEFAULT_MAX_DELAY,
+            ))),
+            last_request_at: Rc::new(Cell::new(None)),
+        }
+    }
+
+    /// Paces the requests of every account with `pacing`, including the ones already registered.
+    pub fn with_shared_pacing(self, pacing: AdaptivePacing) -> Self {
+        *self.pacing.borrow_mut() = pacing;
+        self
+    }
+
+    /// Registers `manager` as the manager of `steamid`, replacing the previous one, if any.
+    ///
+    /// The pacing `manager` was built with is replaced by the shared one of the registry.
+    pub fn register(&mut self, steamid: SteamID, manager: OwnedSteamTradeManager) {
+        let manager = manager.with_shared_limiter(Rc::clone(&self.pacing), Rc::clone(&self.last_request_at));
+
+        match self.managers.iter_mut().find(|(registered, _)| *registered == steamid) {
+            Some((_, registered_manager)) => *registered_manager = manager,
+            None => self.managers.push((steamid, manager)),
+        }
+    }
+
+    /// Removes the manager of `steamid`, returning it if it was registered.
+    ///
+    /// The returned manager is still rate limited together with the accounts of the registry.
+    pub fn unregister(&mut self, steamid: &SteamID) -> Option<OwnedSteamTradeManager> {
+        let index = self.managers.iter().position(|(registered, _)| registered == steamid)?;
+        Some(self.managers.remove(index).1)
+    }
+
+    /// Returns the manager of `steamid`, if it is registered.
+    pub fn for_account(&self, steamid: &SteamID) -> Option<&OwnedSteamTradeManager> {
+        self.managers
+            .iter()
+            .find(|(registered, _)| registered == steamid)
+            .map(|(_, manager)| manager)
+    }
+
+    /// SteamIDs of every registered account, in the order they were registered.
+    pub fn accounts(&self) -> impl Iterator<Item = &SteamID> {
+        self.managers.iter().map(|(steamid, _)| steamid)
+    }
+
+    /// Polls the received offers of every account with `SteamTradeManager::poll_received_offers`, up to
+    /// `POLL_CONCURRENCY` accounts at once, and hands them to `handler` along with the account SteamID.
+    ///
+    /// Requests are still spaced out by the shared rate limiter. `handler` is called once every account was polled, in
+    /// the order the accounts were registered.
+    ///
+    /// Returns the accounts that failed to be polled, with their error. They are logged and skipped, and the remaining
+    /// accounts are polled as usual.
+    pub async fn poll_all<F>(&self, mut handler: F) -> Vec<(SteamID, TradeError)>
+    where
+        F: FnMut(&SteamID, Vec<TradeOffer_Trade>),
+    {
+        let mut results = stream::iter(self.managers.iter().enumerate())
+            .map(|(index, (steamid, manager))| async move {
+                (index, steamid, manager.manager().poll_received_offers().await)
+            })
+            .buffer_unordered(POLL_CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await;
+        results.sort_by_key(|(index, _, _)| *index);
+
+        let mut failures = vec![];
+        for (_, steamid, result) in results {
+            match result {
+                Ok(offers) => handler(steamid, offers),
+                Err(e) => {
+                    warn!("Failed to poll the offers of {}: {}", steamid.to_steam64(), e);
+                    failures.push((steamid.clone(), e));
+                }
+            }
+        }
+
+        failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use steam_mobile::User;
+
+    use super::*;
+
+    fn owned_manager() -> OwnedSteamTradeManager {
+        OwnedSteamTradeManager::from_cookies(
+            User::new("username".to_string(), "password".to_string()),
+            "sessionid",
+            "76561198040191316%7C%7Ctoken",
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn managers_share_limiter() {
+        let first = SteamID::from_steam64(76561198040191316);
+        let second = SteamID::from_steam64(76561197984835396);
+
+        let mut registry = TradeManagerRegistry::new();
+        registry.register(first.clone(), owned_manager());
+        registry.register(second.clone(), owned_manager());
+        let registry = registry.with_shared_pacing(AdaptivePacing::new(Duration::from_secs(2), Duration::from_secs(5)));
+
+        let first = registry.for_account(&first).unwrap().manager();
+        let second = registry.for_account(&second).unwrap().manager();
+        assert!(first.config.throttle && second.config.throttle);
+        assert!(Rc::ptr_eq(
+            &first.config.last_request_at,
+            &second.config.last_request_at
+        ));
+        assert_eq!(first.request_delay(), Duration::from_secs(2));
+        assert_eq!(second.request_delay(), Duration::from_secs(2));
+    }
+}
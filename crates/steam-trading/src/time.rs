@@ -7,15 +7,74 @@
 //! steam-trading = { version = "*", features = ["time"] }
 //! ```
 
+use std::cell::Cell;
+use std::fmt;
+
 use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
+use steam_language_gen::generated::enums::ETradeOfferState;
+use tappet::response_types::{TradeHistory_Trade, TradeOffer_Trade};
 
 pub const ONE_HOUR_SECONDS: i64 = 3600;
 pub const ONE_WEEK_SECONDS: i64 = ONE_HOUR_SECONDS * 24 * 7;
+/// Steam expires active offers two weeks after they are created.
+pub const OFFER_LIFETIME_SECONDS: i64 = ONE_WEEK_SECONDS * 2;
+
+const CSGO_APPID: u32 = 730;
+const DOTA2_APPID: u32 = 570;
+const TF2_APPID: u32 = 440;
+const STEAM_APPID: u32 = 753;
 
 // Steam "Midnight" is on 10:00 PST/GMT-8 or 18:00 UTC/GMT.
 const STEAM_MIDNIGHT_OFFSET_UTC_SECONDS: i64 = ONE_HOUR_SECONDS * 18;
 const PST_TO_UTC_OFFSET_SECONDS: i64 = ONE_HOUR_SECONDS * 8;
 
+/// Source of the current time for the helpers that depend on it.
+///
+/// `SystemClock` is used by default. Swap it for a `MockClock` to test time dependent code without depending on the
+/// wall clock.
+pub trait Clock: fmt::Debug {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Reads the current time from the system.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that is stopped at a fixed time, and only moves when told to.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Cell<DateTime<Utc>>,
+}
+
+impl MockClock {
+    /// Creates a clock stopped at the unix time `epoch`.
+    pub fn at(epoch: i64) -> Self {
+        Self {
+            now: Cell::new(Utc.timestamp(epoch, 0)),
+        }
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        self.now.set(now);
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.now.get()
+    }
+}
+
 /// Adds the Steam Midnight offset to a completed trade time epoch
 fn trade_time_with_offset(trade_complete_time_epoch: i64) -> DateTime<Utc> {
     let trade_utc = Utc.timestamp(trade_complete_time_epoch, 0);
@@ -23,6 +82,67 @@ fn trade_time_with_offset(trade_complete_time_epoch: i64) -> DateTime<Utc> {
     trade_utc + Duration::seconds(STEAM_MIDNIGHT_OFFSET_UTC_SECONDS)
 }
 
+/// Returns how long ago `epoch` was.
+pub fn age_since(epoch: i64) -> Duration {
+    age_since_with(&SystemClock, epoch)
+}
+
+/// Same as `age_since`, but with the current time read from `clock`.
+pub fn age_since_with(clock: &dyn Clock, epoch: i64) -> Duration {
+    clock.now() - Utc.timestamp(epoch, 0)
+}
+
+/// Returns when an active offer created on `time_created` will expire.
+pub fn offer_expiration(time_created: i64) -> DateTime<Utc> {
+    Utc.timestamp(time_created, 0) + Duration::seconds(OFFER_LIFETIME_SECONDS)
+}
+
+/// True if an active offer created on `time_created` expires within `within` from now, as read from `clock`.
+pub fn expires_within(time_created: i64, within: Duration, clock: &dyn Clock) -> bool {
+    offer_expiration(time_created) - clock.now() <= within
+}
+
+/// Returns the current state of `offer`, along with when it entered it.
+///
+/// Steam does not keep the states an offer went through, only when its state last changed.
+pub fn offer_state_since(offer: &TradeOffer_Trade) -> (ETradeOfferState, DateTime<Utc>) {
+    (offer.state, Utc.timestamp(offer.time_updated, 0))
+}
+
+/// Returns when the items of an escrowed trade offer will be released, if it is being held.
+///
+/// Steam sets `escrow_end_date` to zero for offers that are not being held.
+pub fn escrow_release_date(escrow_end_date: i64) -> Option<DateTime<Utc>> {
+    if escrow_end_date > 0 {
+        Some(Utc.timestamp(escrow_end_date, 0))
+    } else {
+        None
+    }
+}
+
+/// Returns for how long items of `appid` are held after being traded.
+///
+/// Apps not known are assumed to hold items for a week, so the estimate is never too early.
+pub fn tradelock_duration_for_app(appid: u32) -> Duration {
+    match appid {
+        CSGO_APPID | DOTA2_APPID => Duration::seconds(ONE_WEEK_SECONDS),
+        TF2_APPID | STEAM_APPID => Duration::zero(),
+        _ => Duration::seconds(ONE_WEEK_SECONDS),
+    }
+}
+
+/// Same as `estimate_tradelock_end`, but with the trade hold of `appid`.
+///
+/// Items of apps without a trade hold are tradable as soon as the trade is completed.
+pub fn estimate_tradelock_end_for_app(trade_completed_on_epoch: i64, appid: u32) -> NaiveDateTime {
+    let trade_lock_duration = tradelock_duration_for_app(appid);
+
+    if trade_lock_duration <= Duration::zero() {
+        return Utc.timestamp(trade_completed_on_epoch, 0).naive_utc();
+    }
+    estimate_tradelock_end(trade_completed_on_epoch, trade_lock_duration.num_seconds())
+}
+
 pub fn estimate_tradelock_end(trade_completed_on_epoch: i64, trade_lock_duration_seconds: i64) -> NaiveDateTime {
     let trade_with_offset = trade_time_with_offset(trade_completed_on_epoch);
     let trade_lock_duration = Duration::seconds(trade_lock_duration_seconds);
@@ -32,6 +152,24 @@ pub fn estimate_tradelock_end(trade_completed_on_epoch: i64, trade_lock_duration
     tradelock_end_datetime.naive_utc()
 }
 
+/// Estimates when each item received on `trade` can be traded again, keyed by its new asset id.
+///
+/// The trade hold of each item depends on its app, and starts when the trade settles, which is after the escrow period
+/// if there was one.
+pub fn received_tradelock_ends(trade: &TradeHistory_Trade) -> Vec<(i64, DateTime<Utc>)> {
+    let settled_on = trade.time_escrow_end.unwrap_or(trade.time_init);
+
+    trade
+        .assets_received
+        .iter()
+        .flatten()
+        .map(|asset| {
+            let tradelock_end = estimate_tradelock_end_for_app(settled_on, asset.appid);
+            (asset.new_assetid, DateTime::from_utc(tradelock_end, Utc))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,6 +182,55 @@ mod tests {
         1604649600
     }
 
+    #[test]
+    fn t_age_since() {
+        let one_hour_ago = Utc::now().timestamp() - ONE_HOUR_SECONDS;
+        let age = age_since(one_hour_ago);
+        assert!(age.num_seconds() >= ONE_HOUR_SECONDS && age.num_seconds() < ONE_HOUR_SECONDS + 60);
+    }
+
+    #[test]
+    fn t_mock_clock() {
+        let clock = MockClock::at(trade_complete_time_sample());
+        assert_eq!(age_since_with(&clock, trade_complete_time_sample()), Duration::zero());
+
+        clock.advance(Duration::seconds(ONE_HOUR_SECONDS));
+        assert_eq!(
+            age_since_with(&clock, trade_complete_time_sample()).num_seconds(),
+            ONE_HOUR_SECONDS
+        );
+    }
+
+    #[test]
+    fn t_expires_within() {
+        let clock = MockClock::at(1603997472);
+        assert!(!expires_within(1603997472, Duration::days(13), &clock));
+        assert!(expires_within(1603997472, Duration::days(14), &clock));
+
+        clock.advance(Duration::days(2));
+        assert!(expires_within(1603997472, Duration::days(13), &clock));
+    }
+
+    #[test]
+    fn t_offer_expiration() {
+        assert_eq!(offer_expiration(1603997472).timestamp(), 1605207072);
+    }
+
+    #[test]
+    fn t_escrow_release_date() {
+        assert_eq!(escrow_release_date(0), None);
+        assert_eq!(escrow_release_date(1604649600).unwrap().timestamp(), 1604649600);
+    }
+
+    #[test]
+    fn t_estimate_for_app() {
+        let estimated = estimate_tradelock_end_for_app(trade_complete_time_sample(), 730);
+        assert_eq!(estimated.timestamp(), expected_tradelock_end());
+
+        let estimated = estimate_tradelock_end_for_app(trade_complete_time_sample(), 440);
+        assert_eq!(estimated.timestamp(), trade_complete_time_sample());
+    }
+
     #[test]
     fn t_estimate() {
         let estimated = estimate_tradelock_end(trade_complete_time_sample(), ONE_WEEK_SECONDS);
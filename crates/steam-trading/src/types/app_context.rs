@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+/// Primary contextids of common apps, used when an asset is added without one.
+const DEFAULT_CONTEXTS: &[(u32, u32)] = &[
+    // Counter-Strike: Global Offensive
+    (730, 2),
+    // Team Fortress 2
+    (440, 2),
+    // Dota 2
+    (570, 2),
+    // Rust
+    (252490, 2),
+    // Steam, such as trading cards and backgrounds
+    (753, 6),
+];
+
+/// Registry of the contextid items of each app are usually traded from.
+///
+/// Starts with the primary contexts of common apps, and can be overridden or extended with `with_context`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppContext(HashMap<u32, u32>);
+
+impl AppContext {
+    /// Sets `contextid` as the context of `appid`, replacing the one in the registry, if any.
+    pub fn with_context(mut self, appid: u32, contextid: u32) -> Self {
+        self.0.insert(appid, contextid);
+        self
+    }
+
+    /// Returns the context of `appid`, or `None` if it isn't registered.
+    pub fn contextid(&self, appid: u32) -> Option<u32> {
+        self.0.get(&appid).copied()
+    }
+}
+
+impl Default for AppContext {
+    fn default() -> Self {
+        Self(DEFAULT_CONTEXTS.iter().copied().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_override() {
+        let registry = AppContext::default();
+        assert_eq!(registry.contextid(730), Some(2));
+        assert_eq!(registry.contextid(753), Some(6));
+        assert_eq!(registry.contextid(1), None);
+
+        let registry = registry.with_context(753, 1).with_context(1, 3);
+        assert_eq!(registry.contextid(753), Some(1));
+        assert_eq!(registry.contextid(1), Some(3));
+    }
+}
@@ -1,16 +1,98 @@
-use crate::types::trade_offer_web::{Asset, AssetList};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct AssetCollection(pub(crate) Vec<Asset>);
+use crate::errors::OfferValidationError;
+use crate::types::app_context::AppContext;
+use crate::types::inventory::Inventory;
+use crate::types::trade_offer_web::{Asset, AssetList, CurrencyAsset};
+
+/// Assets of one side of the trade offer.
+///
+/// Assets can be added by their assetid, or by their classid, if it doesn't matter which copy of a fungible item is
+/// traded. Assets added by classid need to be resolved with `TradeOffer::resolve_fungible` before sending the offer.
+///
+/// Steam currency items are traded by amount, and are added with `add_currency`.
+///
+/// Serialized with named fields, `assets`, `fungible` and `currency`. Collections serialized as a positional array by
+/// older versions can still be read.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(from = "AssetCollectionFields", into = "AssetCollectionFields")]
+pub struct AssetCollection(
+    pub(crate) Vec<Asset>,
+    pub(crate) Vec<FungibleRequest>,
+    pub(crate) Vec<CurrencyAsset>,
+);
+
+/// Serialized form of `AssetCollection`, named so that stored collections don't depend on the order of its fields.
+///
+/// Like any struct, it is also read from a sequence of its fields in order, which is how older versions wrote it.
+#[derive(Serialize, Deserialize)]
+struct AssetCollectionFields {
+    #[serde(default)]
+    assets: Vec<Asset>,
+    #[serde(default)]
+    fungible: Vec<FungibleRequest>,
+    #[serde(default)]
+    currency: Vec<CurrencyAsset>,
+}
+
+impl From<AssetCollection> for AssetCollectionFields {
+    fn from(AssetCollection(assets, fungible, currency): AssetCollection) -> Self {
+        Self {
+            assets,
+            fungible,
+            currency,
+        }
+    }
+}
+
+impl From<AssetCollectionFields> for AssetCollection {
+    fn from(fields: AssetCollectionFields) -> Self {
+        Self(fields.assets, fields.fungible, fields.currency)
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+/// Request of any `amount` assets of `classid`.
+pub(crate) struct FungibleRequest {
+    appid: u32,
+    contextid: u32,
+    classid: i64,
+    amount: i64,
+}
 
 impl AssetCollection {
     pub(crate) fn dump_to_asset_list(self) -> AssetList {
         AssetList {
             assets: self.0,
+            currency: self.2,
             ..Default::default()
         }
     }
 
+    /// Builds a collection with every `(appid, contextid, assetid)` of `items`, e.g. as read from a database.
+    pub fn from_tuples<I: IntoIterator<Item = (u32, u32, i64)>>(items: I) -> Self {
+        Self::from_tuples_with_amounts(
+            items
+                .into_iter()
+                .map(|(appid, contextid, assetid)| (appid, contextid, assetid, 1)),
+        )
+    }
+
+    /// Same as `from_tuples`, but with the `(appid, contextid, assetid, amount)` of stackable items.
+    pub fn from_tuples_with_amounts<I: IntoIterator<Item = (u32, u32, i64, i64)>>(items: I) -> Self {
+        let assets = items
+            .into_iter()
+            .map(|(appid, contextid, assetid, amount)| Asset {
+                appid,
+                contextid: contextid.to_string(),
+                amount,
+                assetid: assetid.to_string(),
+            })
+            .collect();
+
+        Self(assets, vec![], vec![])
+    }
+
     pub fn add(&mut self, appid: u32, contextid: u32, assetid: i64) {
         let asset = Asset {
             appid,
@@ -25,11 +107,105 @@ impl AssetCollection {
 
         self.0.push(asset);
     }
+
+    /// Adds `assetid` of `appid`, from the app primary context as found in `AppContext::default`. E.g: 2 for CS:GO.
+    ///
+    /// Fails if the app is not in the registry. Use `add_item_in` with an extended registry for other apps.
+    pub fn add_item(&mut self, appid: u32, assetid: i64) -> Result<(), OfferValidationError> {
+        self.add_item_in(&AppContext::default(), appid, assetid)
+    }
+
+    /// Same as `add_item`, but the context is looked up in `registry`.
+    pub fn add_item_in(&mut self, registry: &AppContext, appid: u32, assetid: i64) -> Result<(), OfferValidationError> {
+        let contextid = registry
+            .contextid(appid)
+            .ok_or_else(|| OfferValidationError::InvalidAsset(format!("No known contextid for appid {}", appid)))?;
+
+        self.add(appid, contextid, assetid);
+        Ok(())
+    }
+
+    /// Adds `amount` items of `classid`, without caring about which specific assets are traded.
+    /// Useful for fungible items, such as keys or cards.
+    pub fn add_by_classid(&mut self, appid: u32, contextid: u32, classid: i64, amount: i64) {
+        self.1.push(FungibleRequest {
+            appid,
+            contextid,
+            classid,
+            amount,
+        });
+    }
+
+    /// Adds `amount` of the Steam currency item `currencyid`.
+    pub fn add_currency(&mut self, appid: u32, contextid: u32, currencyid: i64, amount: i64) {
+        self.2.push(CurrencyAsset {
+            appid,
+            contextid: contextid.to_string(),
+            currencyid: currencyid.to_string(),
+            amount,
+        });
+    }
+
+    /// Number of assets and currencies in this collection, not counting the ones added by classid.
+    pub(crate) fn item_count(&self) -> usize {
+        self.0.len() + self.2.len()
+    }
+
+    /// True if there are assets added by classid that were not resolved into assetids yet.
+    pub(crate) fn has_unresolved_fungible(&self) -> bool {
+        !self.1.is_empty()
+    }
+
+    /// Picks concrete tradable assets from `inventory` for every asset added by classid.
+    pub(crate) fn resolve_fungible(&self, inventory: &Inventory) -> Result<Self, OfferValidationError> {
+        let mut resolved = Self(self.0.clone(), vec![], self.2.clone());
+
+        for request in &self.1 {
+            let mut remaining = request.amount;
+
+            let candidates = inventory
+                .tradable_assets_of_class(request.classid)
+                .filter(|asset| asset.appid == request.appid && asset.contextid == request.contextid);
+
+            for inventory_asset in candidates {
+                if remaining <= 0 {
+                    break;
+                }
+
+                let assetid = inventory_asset.assetid.to_string();
+                if resolved.0.iter().any(|asset| asset.assetid == assetid) {
+                    continue;
+                }
+
+                let amount = remaining.min(inventory_asset.amount);
+                resolved.0.push(Asset {
+                    appid: request.appid,
+                    contextid: request.contextid.to_string(),
+                    amount,
+                    assetid,
+                });
+                remaining -= amount;
+            }
+
+            if remaining > 0 {
+                return Err(OfferValidationError::NotEnoughItems {
+                    classid: request.classid,
+                    missing: remaining,
+                });
+            }
+        }
+
+        Ok(resolved)
+    }
 }
 
 impl Default for AssetCollection {
     fn default() -> Self {
-        Self { 0: vec![] }
+        Self {
+            0: vec![],
+            1: vec![],
+            2: vec![],
+        }
     }
 }
 
@@ -44,6 +220,97 @@ mod tests {
         my_assets.add(730, 2, 18465222145);
         println!("{:?}", serde_json::to_string(&my_assets.dump_to_asset_list()));
     }
+
+    #[test]
+    fn from_tuples_round_trip() {
+        let items = vec![(570, 2, 17034419698), (730, 2, 18465222145)];
+        let collection = AssetCollection::from_tuples(items.clone());
+
+        let mut manual = AssetCollection::default();
+        items
+            .iter()
+            .for_each(|&(appid, contextid, assetid)| manual.add(appid, contextid, assetid));
+        assert_eq!(collection, manual);
+
+        let stackables = vec![(753, 6, 21196437815, 5)];
+        let collection = AssetCollection::from_tuples_with_amounts(stackables.clone());
+        let tuples = collection
+            .0
+            .iter()
+            .map(|asset| {
+                (
+                    asset.appid,
+                    asset.contextid.parse::<u32>().unwrap(),
+                    asset.assetid.parse::<i64>().unwrap(),
+                    asset.amount,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(tuples, stackables);
+    }
+
+    #[test]
+    fn serde_named_fields() {
+        let mut collection = AssetCollection::from_tuples(vec![(730, 2, 17034419698)]);
+        collection.add_by_classid(730, 2, 1690096482, 2);
+        collection.add_currency(753, 3, 1, 250);
+
+        let json = serde_json::to_value(&collection).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "assets": [{ "appid": 730, "contextid": "2", "amount": 1, "assetid": "17034419698" }],
+                "fungible": [{ "appid": 730, "contextid": 2, "classid": 1690096482, "amount": 2 }],
+                "currency": [{ "appid": 753, "contextid": "3", "currencyid": "1", "amount": 250 }]
+            })
+        );
+        assert_eq!(serde_json::from_value::<AssetCollection>(json).unwrap(), collection);
+
+        let positional = r#"[[{ "appid": 730, "contextid": "2", "amount": 1, "assetid": "17034419698" }], []]"#;
+        assert_eq!(
+            serde_json::from_str::<AssetCollection>(positional).unwrap(),
+            AssetCollection::from_tuples(vec![(730, 2, 17034419698)])
+        );
+    }
+
+    #[test]
+    fn add_item_default_context() {
+        let mut my_assets = AssetCollection::default();
+        my_assets.add_item(730, 17034419698).unwrap();
+        assert_eq!(my_assets.0[0].contextid, "2");
+
+        assert!(my_assets.add_item(1, 17034419698).is_err());
+        my_assets
+            .add_item_in(&AppContext::default().with_context(1, 3), 1, 17034419698)
+            .unwrap();
+        assert_eq!(my_assets.0[1].contextid, "3");
+    }
+
+    #[test]
+    fn resolve_by_classid() {
+        let inventory = serde_json::from_str::<Inventory>(
+            r#"{
+  "assets": [
+    { "appid": 730, "contextid": "2", "assetid": "19793871926", "classid": "1690096482", "instanceid": "0", "amount": "1" },
+    { "appid": 730, "contextid": "2", "assetid": "19793871927", "classid": "1690096482", "instanceid": "0", "amount": "1" },
+    { "appid": 730, "contextid": "2", "assetid": "19793871928", "classid": "1690096482", "instanceid": "0", "amount": "1" }
+  ]
+}"#,
+        )
+        .unwrap();
+
+        let mut my_assets = AssetCollection::default();
+        my_assets.add(730, 2, 19793871926);
+        my_assets.add_by_classid(730, 2, 1690096482, 2);
+        assert!(my_assets.has_unresolved_fungible());
+
+        let resolved = my_assets.resolve_fungible(&inventory).unwrap();
+        assert!(!resolved.has_unresolved_fungible());
+        assert_eq!(resolved.0.len(), 3);
+
+        my_assets.add_by_classid(730, 2, 1690096482, 1);
+        assert!(my_assets.resolve_fungible(&inventory).is_err());
+    }
 }
 
 // 1 ask -> version 2
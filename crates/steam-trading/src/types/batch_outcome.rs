@@ -0,0 +1,73 @@
+use std::iter::FromIterator;
+
+use crate::TradeError;
+
+/// Result of an operation applied to many inputs, such as `SteamTradeManager::create_offers`.
+///
+/// Every result is keyed by the index of its input, so failures can be matched with what caused them.
+#[derive(Debug)]
+pub struct BatchOutcome<T> {
+    pub successes: Vec<(usize, T)>,
+    pub failures: Vec<(usize, TradeError)>,
+}
+
+impl<T> BatchOutcome<T> {
+    /// True if no input failed, including if there was no input at all.
+    pub fn all_succeeded(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Discards the successes, and returns every failure with the index of its input.
+    pub fn into_errors(self) -> Vec<(usize, TradeError)> {
+        self.failures
+    }
+
+    pub(crate) fn push(&mut self, index: usize, result: Result<T, TradeError>) {
+        match result {
+            Ok(success) => self.successes.push((index, success)),
+            Err(error) => self.failures.push((index, error)),
+        }
+    }
+}
+
+impl<T> Default for BatchOutcome<T> {
+    fn default() -> Self {
+        Self {
+            successes: vec![],
+            failures: vec![],
+        }
+    }
+}
+
+/// Results are keyed by their position in the iterator.
+impl<T> FromIterator<Result<T, TradeError>> for BatchOutcome<T> {
+    fn from_iter<I: IntoIterator<Item = Result<T, TradeError>>>(iter: I) -> Self {
+        let mut outcome = Self::default();
+        iter.into_iter()
+            .enumerate()
+            .for_each(|(index, result)| outcome.push(index, result));
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OfferError;
+
+    #[test]
+    fn outcome_keyed_by_index() {
+        let outcome = vec![Ok(1), Err(TradeError::from(OfferError::NoMatch)), Ok(3)]
+            .into_iter()
+            .collect::<BatchOutcome<i64>>();
+
+        assert!(!outcome.all_succeeded());
+        assert_eq!(outcome.successes, vec![(0, 1), (2, 3)]);
+
+        let errors = outcome.into_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, 1);
+
+        assert!(BatchOutcome::<()>::default().all_succeeded());
+    }
+}
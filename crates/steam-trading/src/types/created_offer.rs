@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+
+use crate::errors::OfferError;
+use crate::types::asset_collection::AssetCollection;
+use crate::types::trade_offer::TradeOffer;
+use crate::types::trade_offer_id::TradeOfferId;
+use crate::types::trade_offer_web::{Asset, TradeOfferCreateResponse};
+
+/// A trade offer that was successfully created.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreatedOffer {
+    /// Trade offer id of the new offer.
+    pub id: i64,
+    /// True if the offer will only be sent to the partner after being confirmed, by mobile or email.
+    pub needs_confirmation: bool,
+    /// True if the account uses email Steam Guard, and the offer must be confirmed by email instead of mobile.
+    pub needs_email_confirmation: bool,
+    /// Masked domain of the email the confirmation was sent to, e.g. "gmail.com".
+    pub email_domain: Option<String>,
+    /// Unix epoch when the items are released, if Steam will hold them in escrow once the offer is accepted.
+    pub escrow_end: Option<i64>,
+    /// Items from our inventory, as Steam accepted them into the offer, if it echoed them back.
+    pub items_to_give: Option<Vec<Asset>>,
+    /// Items from the partner inventory, as Steam accepted them into the offer, if it echoed them back.
+    pub items_to_receive: Option<Vec<Asset>>,
+}
+
+impl CreatedOffer {
+    pub(crate) fn from_response(response: TradeOfferCreateResponse) -> Result<Self, OfferError> {
+        let TradeOfferId(id) = response
+            .tradeofferid
+            .ok_or_else(|| OfferError::GeneralFailure("Steam did not return the created trade offer id.".to_string()))?
+            .parse::<TradeOfferId>()
+            .map_err(|e| OfferError::GeneralFailure(format!("Steam returned an invalid trade offer id: {}", e)))?;
+
+        let needs_email_confirmation = response.needs_email_confirmation.unwrap_or(false);
+        let needs_confirmation = response.needs_mobile_confirmation.unwrap_or(false) || needs_email_confirmation;
+
+        Ok(Self {
+            id,
+            needs_confirmation,
+            needs_email_confirmation,
+            email_domain: response.email_domain.filter(|domain| !domain.is_empty()),
+            escrow_end: response.escrow_end_date.filter(|&escrow_end| escrow_end > 0),
+            items_to_give: response.items_to_give,
+            items_to_receive: response.items_to_receive,
+        })
+    }
+
+    /// Checks that Steam accepted exactly the items of `tradeoffer`, the offer that was sent, on both sides.
+    ///
+    /// Returns `None` if Steam did not echo the items back. The order of the items does not matter.
+    pub fn items_match(&self, tradeoffer: &TradeOffer) -> Option<bool> {
+        if self.items_to_give.is_none() && self.items_to_receive.is_none() {
+            return None;
+        }
+
+        Some(
+            sent_keys(&tradeoffer.my_assets) == echoed_keys(&self.items_to_give)
+                && sent_keys(&tradeoffer.their_assets) == echoed_keys(&self.items_to_receive),
+        )
+    }
+}
+
+/// Identity of every asset of one side of the offer that was sent.
+fn sent_keys(assets: &Option<AssetCollection>) -> Vec<(u32, &str, &str, i64)> {
+    asset_keys(assets.as_ref().map_or(&[][..], |assets| &assets.0))
+}
+
+/// Identity of every asset of one side of the offer, as echoed back by Steam.
+fn echoed_keys(items: &Option<Vec<Asset>>) -> Vec<(u32, &str, &str, i64)> {
+    asset_keys(items.as_deref().unwrap_or_default())
+}
+
+/// Identity of every asset of `assets`, sorted so they can be compared regardless of order.
+fn asset_keys(assets: &[Asset]) -> Vec<(u32, &str, &str, i64)> {
+    let mut keys = assets
+        .iter()
+        .map(|asset| (asset.appid, &*asset.contextid, &*asset.assetid, asset.amount))
+        .collect::<Vec<_>>();
+    keys.sort_unstable();
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn created_offer_from_response() {
+        let response = r#"{"tradeofferid":"4112828817","needs_mobile_confirmation":true,"needs_email_confirmation":false,"email_domain":"gmail.com"}"#;
+        let response = serde_json::from_str::<TradeOfferCreateResponse>(response).unwrap();
+        let created = CreatedOffer::from_response(response).unwrap();
+        assert_eq!(created.id, 4112828817);
+        assert!(created.needs_confirmation);
+        assert!(!created.needs_email_confirmation);
+
+        let response = serde_json::from_str::<TradeOfferCreateResponse>(r#"{"tradeofferid":"4112828817"}"#).unwrap();
+        assert!(!CreatedOffer::from_response(response).unwrap().needs_confirmation);
+    }
+
+    #[test]
+    fn created_offer_needs_email_confirmation() {
+        let response = r#"{"tradeofferid":"4112828817","needs_mobile_confirmation":false,"needs_email_confirmation":true,"email_domain":"gmail.com"}"#;
+        let response = serde_json::from_str::<TradeOfferCreateResponse>(response).unwrap();
+        let created = CreatedOffer::from_response(response).unwrap();
+        assert!(created.needs_confirmation);
+        assert!(created.needs_email_confirmation);
+        assert_eq!(created.email_domain.as_deref(), Some("gmail.com"));
+    }
+
+    #[test]
+    fn created_offer_items_match() {
+        let mut my_assets = AssetCollection::default();
+        my_assets.add(730, 2, 17034419698);
+        my_assets.add(730, 2, 17034419699);
+        let tradeoffer = TradeOffer::new(
+            "https://steamcommunity.com/tradeoffer/new/?partner=79925588&token=Ob27qXzn".to_string(),
+            my_assets,
+            None,
+            "".to_string(),
+        )
+        .unwrap();
+
+        let created_with = |items_to_give: &str| {
+            let response = format!(r#"{{"tradeofferid":"4112828817","items_to_give":{}}}"#, items_to_give);
+            CreatedOffer::from_response(serde_json::from_str(&response).unwrap()).unwrap()
+        };
+
+        let created = created_with(
+            r#"[{"appid":730,"contextid":"2","amount":1,"assetid":"17034419699"},
+                {"appid":730,"contextid":"2","amount":1,"assetid":"17034419698"}]"#,
+        );
+        assert_eq!(created.items_match(&tradeoffer), Some(true));
+
+        let created = created_with(r#"[{"appid":730,"contextid":"2","amount":1,"assetid":"17034419698"}]"#);
+        assert_eq!(created.items_match(&tradeoffer), Some(false));
+
+        let response = serde_json::from_str(r#"{"tradeofferid":"4112828817"}"#).unwrap();
+        assert_eq!(
+            CreatedOffer::from_response(response).unwrap().items_match(&tradeoffer),
+            None
+        );
+    }
+
+    #[test]
+    fn created_offer_in_escrow() {
+        let response = r#"{"tradeofferid":"4112828817","needs_mobile_confirmation":true,"escrow_end_date":1605295702}"#;
+        let response = serde_json::from_str::<TradeOfferCreateResponse>(response).unwrap();
+        assert_eq!(
+            CreatedOffer::from_response(response).unwrap().escrow_end,
+            Some(1605295702)
+        );
+
+        let response = r#"{"tradeofferid":"4112828817","escrow_end_date":0}"#;
+        let response = serde_json::from_str::<TradeOfferCreateResponse>(response).unwrap();
+        assert_eq!(CreatedOffer::from_response(response).unwrap().escrow_end, None);
+    }
+}
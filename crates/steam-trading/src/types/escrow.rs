@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use tappet::response_types::GetTradeHoldDurations;
+
+const ONE_DAY_SECONDS: i64 = 60 * 60 * 24;
+
+/// Estimate of the trade hold that would be applied if an offer were sent now.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EscrowEstimate {
+    /// True if the items would be held for either party.
+    pub will_escrow: bool,
+    /// Days our items would be held for.
+    pub my_days: u32,
+    /// Days the partner items would be held for.
+    pub their_days: u32,
+}
+
+impl EscrowEstimate {
+    pub(crate) fn from_seconds(my_escrow_seconds: i64, their_escrow_seconds: i64) -> Self {
+        let my_days = seconds_to_days(my_escrow_seconds);
+        let their_days = seconds_to_days(their_escrow_seconds);
+
+        Self {
+            will_escrow: my_escrow_seconds > 0 || their_escrow_seconds > 0,
+            my_days,
+            their_days,
+        }
+    }
+}
+
+/// Returns until when our own items are held, as a unix epoch, if trades of this account are being held regardless of
+/// the partner.
+pub(crate) fn own_escrow_hold_end(durations: &GetTradeHoldDurations, now: i64) -> Option<i64> {
+    let my_escrow = durations.my_escrow.as_ref()?;
+    if my_escrow.escrow_end_duration_seconds <= 0 {
+        return None;
+    }
+
+    Some(
+        my_escrow
+            .escrow_end_date
+            .unwrap_or(now + my_escrow.escrow_end_duration_seconds),
+    )
+}
+
+/// Returns in how many days the items of an offer are released, if Steam set `escrow_end_date` in the future.
+pub(crate) fn escrow_days_left(escrow_end_date: i64, now: i64) -> Option<u32> {
+    if escrow_end_date > now {
+        Some(seconds_to_days(escrow_end_date - now))
+    } else {
+        None
+    }
+}
+
+/// Rounds up, so a hold of a few hours still counts as a day.
+fn seconds_to_days(seconds: i64) -> u32 {
+    ((seconds.max(0) + ONE_DAY_SECONDS - 1) / ONE_DAY_SECONDS) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escrow_from_seconds() {
+        let estimate = EscrowEstimate::from_seconds(0, 1_296_000);
+        assert!(estimate.will_escrow);
+        assert_eq!(estimate.my_days, 0);
+        assert_eq!(estimate.their_days, 15);
+
+        let estimate = EscrowEstimate::from_seconds(0, 0);
+        assert!(!estimate.will_escrow);
+    }
+
+    #[test]
+    fn days_left() {
+        assert_eq!(escrow_days_left(1605295702, 1604690902), Some(7));
+        assert_eq!(escrow_days_left(1605295702, 1605295000), Some(1));
+        assert_eq!(escrow_days_left(0, 1604690902), None);
+    }
+
+    #[test]
+    fn own_account_hold() {
+        let durations = serde_json::from_str::<GetTradeHoldDurations>(
+            r#"{
+  "my_escrow": { "escrow_end_duration_seconds": 604800, "escrow_end_date": 1605295702 },
+  "their_escrow": { "escrow_end_duration_seconds": 0 },
+  "both_escrow": { "escrow_end_duration_seconds": 604800, "escrow_end_date": 1605295702 }
+}"#,
+        )
+        .unwrap();
+        assert_eq!(own_escrow_hold_end(&durations, 1604690902), Some(1605295702));
+
+        let mut without_date = durations.clone();
+        without_date.my_escrow.as_mut().unwrap().escrow_end_date = None;
+        assert_eq!(own_escrow_hold_end(&without_date, 1604690902), Some(1605295702));
+
+        let mut partner_only = durations;
+        partner_only.my_escrow.as_mut().unwrap().escrow_end_duration_seconds = 0;
+        assert_eq!(own_escrow_hold_end(&partner_only, 1604690902), None);
+    }
+}
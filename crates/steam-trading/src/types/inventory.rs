@@ -0,0 +1,414 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+
+lazy_static! {
+    /// Inventories of the profile, embedded as JSON at its inventory page.
+    static ref APP_CONTEXT_DATA_REGEX: Regex =
+        Regex::new(r#"(?m)g_rgAppContextData\s*=\s*(\{.*\}|\[\]);\s*$"#).unwrap();
+}
+
+/// Inventory of a single app and context, as returned by the Steam Community inventory endpoint.
+///
+/// Url: https://steamcommunity.com/inventory/76561198040191316/730/2
+///
+/// Assets hold only ids, while everything else about an item, such as its name, is on descriptions shared by every
+/// asset with the same `classid` and `instanceid`. Use `Inventory::items` to iterate both at once.
+///
+/// The legacy format, from `https://steamcommunity.com/profiles/<steamid>/inventory/json/<appid>/<contextid>`, can be
+/// parsed with `Inventory::from_legacy_json`.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Inventory {
+    #[serde(default)]
+    pub assets: Vec<InventoryAsset>,
+    #[serde(default)]
+    pub descriptions: Vec<InventoryDescription>,
+    #[serde(default)]
+    pub total_inventory_count: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// A single asset of the inventory. Items of the same kind share the same `classid`.
+pub struct InventoryAsset {
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub appid: u32,
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub contextid: u32,
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub assetid: i64,
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub classid: i64,
+    /// Some apps don't send it at all.
+    #[serde(default, deserialize_with = "from_str_or_number")]
+    pub instanceid: i64,
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub amount: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Shared information about every asset with the same `classid` and `instanceid`.
+pub struct InventoryDescription {
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub appid: u32,
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub classid: i64,
+    #[serde(default, deserialize_with = "from_str_or_number")]
+    pub instanceid: i64,
+    #[serde(default, deserialize_with = "bool_from_int")]
+    pub tradable: bool,
+    #[serde(default, deserialize_with = "bool_from_int")]
+    pub marketable: bool,
+    pub name: Option<String>,
+    pub market_hash_name: Option<String>,
+}
+
+/// An asset joined with its description.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct InventoryItem<'a> {
+    pub asset: &'a InventoryAsset,
+    pub description: Option<&'a InventoryDescription>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+/// Legacy inventory format. Assets and descriptions are maps instead of lists, and assets don't carry their appid and
+/// contextid.
+struct LegacyInventory {
+    #[serde(rename = "rgInventory", default, deserialize_with = "map_or_empty_list")]
+    assets: HashMap<String, LegacyInventoryAsset>,
+    #[serde(rename = "rgDescriptions", default, deserialize_with = "map_or_empty_list")]
+    descriptions: HashMap<String, InventoryDescription>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LegacyInventoryAsset {
+    #[serde(deserialize_with = "from_str_or_number")]
+    id: i64,
+    #[serde(deserialize_with = "from_str_or_number")]
+    classid: i64,
+    #[serde(default, deserialize_with = "from_str_or_number")]
+    instanceid: i64,
+    #[serde(deserialize_with = "from_str_or_number")]
+    amount: i64,
+}
+
+impl Inventory {
+    /// Parses the legacy inventory format, with `rgInventory` and `rgDescriptions`.
+    ///
+    /// Legacy assets don't carry their `appid` and `contextid`, so they must be the ones requested.
+    pub fn from_legacy_json(json: &str, appid: u32, contextid: u32) -> Result<Self, serde_json::Error> {
+        let legacy = serde_json::from_str::<LegacyInventory>(json)?;
+
+        let assets = legacy
+            .assets
+            .into_iter()
+            .map(|(_, asset)| InventoryAsset {
+                appid,
+                contextid,
+                assetid: asset.id,
+                classid: asset.classid,
+                instanceid: asset.instanceid,
+                amount: asset.amount,
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            total_inventory_count: assets.len() as u32,
+            assets,
+            descriptions: legacy
+                .descriptions
+                .into_iter()
+                .map(|(_, description)| description)
+                .collect(),
+        })
+    }
+
+    /// Moves every asset of `other` into this inventory, e.g. to merge several contexts of the same app.
+    ///
+    /// Descriptions already present are not duplicated.
+    pub fn merge(&mut self, other: Inventory) {
+        for description in other.descriptions {
+            let is_known = self.descriptions.iter().any(|known| {
+                known.appid == description.appid
+                    && known.classid == description.classid
+                    && known.instanceid == description.instanceid
+            });
+            if !is_known {
+                self.descriptions.push(description);
+            }
+        }
+
+        self.assets.extend(other.assets);
+        self.total_inventory_count += other.total_inventory_count;
+    }
+
+    /// Iterates every asset joined with its description.
+    pub fn items(&self) -> impl Iterator<Item = InventoryItem<'_>> {
+        self.assets.iter().map(move |asset| InventoryItem {
+            asset,
+            description: self.description_of(asset),
+        })
+    }
+
+    /// Returns the description of `asset`, if Steam sent one.
+    pub fn description_of(&self, asset: &InventoryAsset) -> Option<&InventoryDescription> {
+        self.descriptions
+            .iter()
+            .find(|description| description.classid == asset.classid && description.instanceid == asset.instanceid)
+    }
+
+    /// Returns every tradable asset of `classid`.
+    /// Assets without a description are considered tradable, and Steam will reject them if they are not.
+    pub fn tradable_assets_of_class(&self, classid: i64) -> impl Iterator<Item = &InventoryAsset> {
+        self.assets.iter().filter(move |asset| {
+            asset.classid == classid
+                && self
+                    .description_of(asset)
+                    .map_or(true, |description| description.tradable)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AppContextData {
+    appid: u32,
+    name: String,
+    #[serde(rename = "rgContexts", default, deserialize_with = "map_or_empty_list")]
+    contexts: HashMap<String, AppContext>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AppContext {
+    #[serde(deserialize_with = "from_str_or_number")]
+    id: u32,
+}
+
+/// Parses every app and context with items, from the `g_rgAppContextData` of a profile inventory page.
+///
+/// Returns (appid, contextid, app name), sorted by appid and contextid.
+pub(crate) fn app_contexts_from_document(document: &str) -> Option<Vec<(u32, u32, String)>> {
+    let json = APP_CONTEXT_DATA_REGEX.captures(document)?.get(1)?.as_str();
+
+    #[derive(Deserialize)]
+    struct Apps(#[serde(deserialize_with = "map_or_empty_list")] HashMap<String, AppContextData>);
+    let Apps(apps) = serde_json::from_str(json).ok()?;
+
+    let mut app_contexts = apps
+        .into_iter()
+        .flat_map(|(_, app)| {
+            let AppContextData { appid, name, contexts } = app;
+            contexts
+                .into_iter()
+                .map(move |(_, context)| (appid, context.id, name.clone()))
+        })
+        .collect::<Vec<_>>();
+    app_contexts.sort();
+
+    Some(app_contexts)
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber<T> {
+    String(String),
+    Number(T),
+}
+
+/// Ids are sent as strings by most apps, but as numbers by a few others.
+fn from_str_or_number<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr + Deserialize<'de>,
+    T::Err: Display,
+{
+    match StringOrNumber::<T>::deserialize(deserializer)? {
+        StringOrNumber::String(value) => value.parse().map_err(D::Error::custom),
+        StringOrNumber::Number(value) => Ok(value),
+    }
+}
+
+/// Legacy inventory sends an empty list, instead of an empty map, if there is nothing to send.
+fn map_or_empty_list<'de, D, V>(deserializer: D) -> Result<HashMap<String, V>, D::Error>
+where
+    D: Deserializer<'de>,
+    V: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum MapOrList<V> {
+        Map(HashMap<String, V>),
+        List(Vec<V>),
+    }
+
+    match MapOrList::<V>::deserialize(deserializer)? {
+        MapOrList::Map(map) => Ok(map),
+        MapOrList::List(_) => Ok(HashMap::new()),
+    }
+}
+
+/// Steam inventory sends booleans as 0 or 1.
+fn bool_from_int<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrInt {
+        Bool(bool),
+        Int(u8),
+    }
+
+    Ok(match BoolOrInt::deserialize(deserializer)? {
+        BoolOrInt::Bool(value) => value,
+        BoolOrInt::Int(value) => value != 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_inventory() -> Inventory {
+        let response = r#"{
+  "assets": [
+    {
+      "appid": 730,
+      "contextid": "2",
+      "assetid": "19793871926",
+      "classid": "1690096482",
+      "instanceid": "0",
+      "amount": "1"
+    },
+    {
+      "appid": 730,
+      "contextid": "2",
+      "assetid": "19793871927",
+      "classid": "1690096482",
+      "instanceid": "0",
+      "amount": "1"
+    },
+    {
+      "appid": 730,
+      "contextid": "2",
+      "assetid": "19793871928",
+      "classid": "1690096482",
+      "instanceid": "143865972",
+      "amount": "1"
+    }
+  ],
+  "descriptions": [
+    {
+      "appid": 730,
+      "classid": "1690096482",
+      "instanceid": "0",
+      "tradable": 1,
+      "marketable": 1,
+      "name": "CS:GO Case Key",
+      "market_hash_name": "CS:GO Case Key"
+    },
+    {
+      "appid": 730,
+      "classid": "1690096482",
+      "instanceid": "143865972",
+      "tradable": 0,
+      "marketable": 0,
+      "name": "CS:GO Case Key",
+      "market_hash_name": "CS:GO Case Key"
+    }
+  ],
+  "total_inventory_count": 3,
+  "success": 1,
+  "rwgrsn": -2
+}"#;
+        serde_json::from_str::<Inventory>(response).unwrap()
+    }
+
+    #[test]
+    fn items_joined_with_descriptions() {
+        let inventory = get_inventory();
+        let untradable = inventory
+            .items()
+            .filter(|item| !item.description.unwrap().tradable)
+            .collect::<Vec<_>>();
+        assert_eq!(untradable.len(), 1);
+        assert_eq!(untradable[0].asset.assetid, 19793871928);
+    }
+
+    #[test]
+    fn legacy_inventory() {
+        let response = r#"{
+  "success": true,
+  "rgInventory": {
+    "8186238410": { "id": "8186238410", "classid": "101785959", "instanceid": "11040578", "amount": "1", "pos": 1 }
+  },
+  "rgCurrency": [],
+  "rgDescriptions": {
+    "101785959_11040578": {
+      "appid": "440",
+      "classid": "101785959",
+      "instanceid": "11040578",
+      "tradable": 1,
+      "marketable": 1,
+      "name": "Mann Co. Supply Crate Key",
+      "market_hash_name": "Mann Co. Supply Crate Key"
+    }
+  },
+  "more": false,
+  "more_start": false
+}"#;
+        let inventory = Inventory::from_legacy_json(response, 440, 2).unwrap();
+        let item = inventory.items().next().unwrap();
+        assert_eq!(item.asset.appid, 440);
+        assert_eq!(item.asset.assetid, 8186238410);
+        assert_eq!(
+            item.description.unwrap().name.as_deref(),
+            Some("Mann Co. Supply Crate Key")
+        );
+
+        let empty =
+            Inventory::from_legacy_json(r#"{"success": true, "rgInventory": [], "rgDescriptions": []}"#, 440, 2);
+        assert_eq!(empty.unwrap(), Inventory::default());
+    }
+
+    #[test]
+    fn app_contexts() {
+        let document = r#"<script type="text/javascript">
+		var g_rgAppContextData = {"730":{"appid":730,"name":"Counter-Strike: Global Offensive","icon":"","link":"https:\/\/steamcommunity.com\/app\/730","asset_count":3,"trade_permissions":"FULL","load_failed":0,"rgContexts":{"2":{"asset_count":3,"id":"2","name":"Backpack"}}},"753":{"appid":753,"name":"Steam","icon":"","link":"https:\/\/steamcommunity.com\/app\/753","asset_count":12,"trade_permissions":"FULL","load_failed":0,"rgContexts":{"6":{"asset_count":11,"id":"6","name":"Community"},"1":{"asset_count":1,"id":"1","name":"Gifts"}}}};
+		var g_strInventoryLoadURL = "https://steamcommunity.com/inventory/76561198040191316/";
+</script>"#;
+        assert_eq!(
+            app_contexts_from_document(document).unwrap(),
+            vec![
+                (730, 2, "Counter-Strike: Global Offensive".to_string()),
+                (753, 1, "Steam".to_string()),
+                (753, 6, "Steam".to_string()),
+            ]
+        );
+
+        let empty = "var g_rgAppContextData = [];\n";
+        assert_eq!(app_contexts_from_document(empty), Some(vec![]));
+        assert_eq!(app_contexts_from_document("<html></html>"), None);
+    }
+
+    #[test]
+    fn merge_contexts() {
+        let mut inventory = get_inventory();
+        let mut other_context = get_inventory();
+        other_context.assets.iter_mut().for_each(|asset| asset.contextid = 6);
+
+        inventory.merge(other_context);
+        assert_eq!(inventory.assets.len(), 6);
+        assert_eq!(inventory.descriptions.len(), 2);
+        assert_eq!(inventory.total_inventory_count, 6);
+        assert_eq!(inventory.assets.iter().filter(|asset| asset.contextid == 6).count(), 3);
+    }
+
+    #[test]
+    fn tradable_assets_of_class() {
+        let inventory = get_inventory();
+        let tradable = inventory.tradable_assets_of_class(1690096482).collect::<Vec<_>>();
+        assert_eq!(tradable.len(), 2);
+        assert!(tradable.iter().all(|asset| asset.instanceid == 0));
+    }
+}
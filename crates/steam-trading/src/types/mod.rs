@@ -1,28 +1,50 @@
-use crate::{TradeOffer, TRADEOFFER_BASE, TRADEOFFER_NEW_URL};
+use crate::types::trade_offer_web::TradeOfferCreateRequest;
+use crate::{TradeOffer, TRADEOFFER_BASE, TRADEOFFER_NEW_TOKEN_URL, TRADEOFFER_NEW_URL};
 
+pub mod app_context;
 pub mod asset_collection;
+pub mod batch_outcome;
+pub mod created_offer;
+pub mod escrow;
+pub mod inventory;
+pub mod net_flow;
+#[cfg(feature = "persona")]
+pub mod persona;
+#[cfg(feature = "persona")]
+pub mod risk_score;
 pub mod sessionid;
+pub mod supported_apps;
 pub mod trade_link;
 pub mod trade_offer;
+pub mod trade_offer_id;
 pub mod trade_offer_web;
+pub mod trade_offers_query;
+pub mod trade_standing;
+pub mod trade_status;
 
 #[derive(Debug, PartialEq)]
 pub enum TradeKind {
-    Accept,
+    /// Accepts an offer made by the partner with this SteamID64.
+    Accept(u64),
     Cancel,
     Create(TradeOffer),
+    /// A create request built by the caller, sent without validation.
+    CreateRaw(TradeOfferCreateRequest),
     Decline,
+    RegenerateToken,
 }
 
 impl TradeKind {
     pub fn endpoint(&self, tradeofferid: Option<i64>) -> String {
-        if let TradeKind::Create(_) = self {
-            return TRADEOFFER_NEW_URL.to_string();
+        match self {
+            TradeKind::Create(_) | TradeKind::CreateRaw(_) => return TRADEOFFER_NEW_URL.to_string(),
+            TradeKind::RegenerateToken => return TRADEOFFER_NEW_TOKEN_URL.to_string(),
+            _ => {}
         }
 
         let tradeofferid = tradeofferid.unwrap();
         let url_path = match self {
-            Self::Accept => "/accept",
+            Self::Accept(_) => "/accept",
             Self::Cancel => "/cancel",
             Self::Decline => "/decline",
             _ => unreachable!(),
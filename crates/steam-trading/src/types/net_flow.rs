@@ -0,0 +1,19 @@
+use tappet::response_types::{TradeHistory_Trade, TradeHistory_TradedAsset};
+
+/// Items that entered and left the inventory with a completed trade.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetFlow {
+    /// Items received from the partner.
+    pub gained: Vec<TradeHistory_TradedAsset>,
+    /// Items given to the partner.
+    pub lost: Vec<TradeHistory_TradedAsset>,
+}
+
+impl From<TradeHistory_Trade> for NetFlow {
+    fn from(trade: TradeHistory_Trade) -> Self {
+        Self {
+            gained: trade.assets_received.unwrap_or_default(),
+            lost: trade.assets_given.unwrap_or_default(),
+        }
+    }
+}
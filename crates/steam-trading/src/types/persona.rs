@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use tappet::response_types::PlayerSummary;
+
+use crate::STEAM_COMMUNITY_BASE;
+
+/// Public profile data of a Steam account, as resolved from GetPlayerSummaries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Persona {
+    /// Current persona name.
+    pub name: String,
+    /// URL of the full size avatar, 184x184 pixels.
+    pub avatar_url: String,
+    /// URL of the community profile.
+    pub profile_url: String,
+}
+
+impl Persona {
+    /// Placeholder for an account Steam returned no summary for, named after its SteamID64.
+    pub(crate) fn unresolved(steamid64: u64) -> Self {
+        Self {
+            name: steamid64.to_string(),
+            avatar_url: String::new(),
+            profile_url: format!("{}/profiles/{}/", STEAM_COMMUNITY_BASE, steamid64),
+        }
+    }
+}
+
+impl From<PlayerSummary> for Persona {
+    fn from(player: PlayerSummary) -> Self {
+        Self {
+            name: player.personaname,
+            avatar_url: player.avatarfull,
+            profile_url: player.profileurl,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_player_summary() {
+        let player = PlayerSummary {
+            steamid: "76561198040191316".to_string(),
+            personaname: "Martin".to_string(),
+            profileurl: "https://steamcommunity.com/id/martin/".to_string(),
+            avatarfull: "https://avatars.akamai.steamstatic.com/5e6f_full.jpg".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Persona::from(player),
+            Persona {
+                name: "Martin".to_string(),
+                avatar_url: "https://avatars.akamai.steamstatic.com/5e6f_full.jpg".to_string(),
+                profile_url: "https://steamcommunity.com/id/martin/".to_string(),
+            }
+        );
+        assert_eq!(
+            Persona::unresolved(76561198040191316).profile_url,
+            "https://steamcommunity.com/profiles/76561198040191316/"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use tappet::response_types::{CEcon_Asset, TradeOffer_Trade};
+
+/// Partner accounts younger than this, in days, are considered brand new.
+const NEW_ACCOUNT_DAYS: u32 = 30;
+
+/// Offers are considered imbalanced if what is received is worth less than this share of what is given.
+const VALUE_IMBALANCE_RATIO: f64 = 0.9;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// A signal that an offer may be a scam, such as one sent by an account impersonating a known trader.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RiskFactor {
+    /// Items worth `value` would be given, and nothing received.
+    OneSidedGive { value: f64 },
+    /// The partner account was created `age_days` ago.
+    NewAccount { age_days: u32 },
+    /// This account never traded with the partner before.
+    NoTradeHistory,
+    /// What would be received is worth `delta` less than what would be given.
+    ValueImbalance { delta: f64 },
+}
+
+impl RiskFactor {
+    /// Points this factor adds to a `RiskScore`, out of 100 for all of them.
+    fn weight(&self) -> u32 {
+        match self {
+            RiskFactor::OneSidedGive { .. } => 40,
+            RiskFactor::NewAccount { .. } => 25,
+            RiskFactor::ValueImbalance { .. } => 20,
+            RiskFactor::NoTradeHistory => 15,
+        }
+    }
+}
+
+/// How risky an offer looks, from 0 to 100, along with the factors that contributed to it.
+///
+/// It is a heuristic, meant to flag offers for manual review, not to decide on its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskScore {
+    pub score: u32,
+    pub factors: Vec<RiskFactor>,
+}
+
+impl RiskScore {
+    /// Scores `offer`, given the unix time the partner account was created at, if known, and whether this account
+    /// traded with the partner before.
+    pub(crate) fn assess<F: Fn(&CEcon_Asset) -> f64>(
+        offer: &TradeOffer_Trade,
+        price_fn: F,
+        partner_created_at: Option<i64>,
+        traded_before: bool,
+        now: i64,
+    ) -> Self {
+        let value_of = |assets: &Option<Vec<CEcon_Asset>>| -> f64 {
+            assets
+                .iter()
+                .flatten()
+                .map(|asset| price_fn(asset) * asset.amount as f64)
+                .sum()
+        };
+        let given = value_of(&offer.items_to_give);
+        let received = value_of(&offer.items_to_receive);
+        let receives_nothing = offer.items_to_receive.as_ref().map_or(true, Vec::is_empty);
+
+        let mut factors = vec![];
+        if receives_nothing && given > 0.0 {
+            factors.push(RiskFactor::OneSidedGive { value: given });
+        } else if received < given * VALUE_IMBALANCE_RATIO {
+            factors.push(RiskFactor::ValueImbalance {
+                delta: given - received,
+            });
+        }
+
+        let age_days = partner_created_at.map(|created_at| ((now - created_at).max(0) / SECONDS_PER_DAY) as u32);
+        if let Some(age_days) = age_days.filter(|&age_days| age_days < NEW_ACCOUNT_DAYS) {
+            factors.push(RiskFactor::NewAccount { age_days });
+        }
+        if !traded_before {
+            factors.push(RiskFactor::NoTradeHistory);
+        }
+
+        Self {
+            score: factors.iter().map(RiskFactor::weight).sum(),
+            factors,
+        }
+    }
+
+    /// True if any factor contributed to the score.
+    pub fn is_risky(&self) -> bool {
+        !self.factors.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tappet::response_types::GetTradeOfferResponse;
+
+    use super::*;
+
+    fn sample_offer(items_to_receive: &str) -> TradeOffer_Trade {
+        let response = format!(
+            r#"{{
+  "response": {{
+    "offer": {{
+      "tradeofferid": "4278637801",
+      "accountid_other": 24569668,
+      "message": "",
+      "expiration_time": 1605208011,
+      "trade_offer_state": 2,
+      "items_to_give": [
+        {{
+          "appid": 730,
+          "contextid": "2",
+          "assetid": "15319724006",
+          "classid": "3035569977",
+          "instanceid": "302028390",
+          "amount": "1",
+          "missing": false,
+          "est_usd": "1"
+        }}
+      ],
+      "items_to_receive": [{}],
+      "is_our_offer": false,
+      "time_created": 1603998411,
+      "time_updated": 1603998411,
+      "from_real_time_trade": false,
+      "escrow_end_date": 0,
+      "confirmation_method": 0
+    }}
+  }}
+}}"#,
+            items_to_receive
+        );
+        serde_json::from_str::<GetTradeOfferResponse>(&response)
+            .unwrap()
+            .response
+            .offer
+    }
+
+    const RECEIVED_ITEM: &str = r#"{
+  "appid": 440,
+  "contextid": "2",
+  "assetid": "9937692380",
+  "classid": "310779465",
+  "instanceid": "188530139",
+  "amount": "1",
+  "missing": false,
+  "est_usd": "1"
+}"#;
+
+    fn price(asset: &CEcon_Asset) -> f64 {
+        match asset.appid {
+            730 => 100.0,
+            _ => 95.0,
+        }
+    }
+
+    #[test]
+    fn scam_like_offer() {
+        let now = 1603998411;
+        let score = RiskScore::assess(&sample_offer(""), price, Some(now - 2 * SECONDS_PER_DAY), false, now);
+
+        assert_eq!(
+            score.factors,
+            vec![
+                RiskFactor::OneSidedGive { value: 100.0 },
+                RiskFactor::NewAccount { age_days: 2 },
+                RiskFactor::NoTradeHistory
+            ]
+        );
+        assert_eq!(score.score, 80);
+    }
+
+    #[test]
+    fn fair_offer_from_known_partner() {
+        let now = 1603998411;
+        let score = RiskScore::assess(&sample_offer(RECEIVED_ITEM), price, Some(1262304000), true, now);
+        assert!(!score.is_risky());
+        assert_eq!(score.score, 0);
+
+        let score = RiskScore::assess(&sample_offer(RECEIVED_ITEM), |_| 1.0, None, true, now);
+        assert!(!score.is_risky());
+    }
+
+    #[test]
+    fn imbalanced_offer() {
+        let now = 1603998411;
+        let score = RiskScore::assess(
+            &sample_offer(RECEIVED_ITEM),
+            |asset| if asset.appid == 730 { 100.0 } else { 10.0 },
+            None,
+            true,
+            now,
+        );
+        assert_eq!(score.factors, vec![RiskFactor::ValueImbalance { delta: 90.0 }]);
+        assert_eq!(score.score, 20);
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use tappet::response_types::TradeOffer_Trade;
+
+use crate::api_extensions::InvolvedAppids;
+
+/// What to do with received offers that trade items of apps outside of `SupportedApps`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum UnsupportedAppPolicy {
+    /// Leave the offer untouched, for someone else to handle it.
+    Skip,
+    /// Decline the offer.
+    Decline,
+}
+
+/// Apps a bot handles, so that offers trading items of other apps are left out, as decided by its policy.
+///
+/// Enable it with `SteamTradeManager::with_supported_apps`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupportedApps {
+    appids: HashSet<u32>,
+    policy: UnsupportedAppPolicy,
+}
+
+impl SupportedApps {
+    pub fn new(appids: &[u32], policy: UnsupportedAppPolicy) -> Self {
+        Self {
+            appids: appids.iter().copied().collect(),
+            policy,
+        }
+    }
+
+    pub fn policy(&self) -> UnsupportedAppPolicy {
+        self.policy
+    }
+
+    /// True if every item of `offer`, on both sides, belongs to a supported app.
+    pub fn supports(&self, offer: &TradeOffer_Trade) -> bool {
+        offer.involved_appids().is_subset(&self.appids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tappet::response_types::GetTradeOfferResponse;
+
+    use super::*;
+
+    #[test]
+    fn supports_offer() {
+        let response = r#"{
+  "response": {
+    "offer": {
+      "tradeofferid": "4278637801",
+      "accountid_other": 24569668,
+      "message": "",
+      "expiration_time": 1605208011,
+      "trade_offer_state": 2,
+      "items_to_give": [
+        {
+          "appid": 570,
+          "contextid": "2",
+          "assetid": "17034419698",
+          "classid": "2521767801",
+          "instanceid": "0",
+          "amount": "1",
+          "missing": false,
+          "est_usd": "3"
+        }
+      ],
+      "items_to_receive": [
+        {
+          "appid": 440,
+          "contextid": "2",
+          "assetid": "9937692380",
+          "classid": "310779465",
+          "instanceid": "188530139",
+          "amount": "1",
+          "missing": false,
+          "est_usd": "1"
+        }
+      ],
+      "is_our_offer": false,
+      "time_created": 1603998411,
+      "time_updated": 1603998411,
+      "from_real_time_trade": false,
+      "escrow_end_date": 0,
+      "confirmation_method": 0
+    }
+  }
+}"#;
+        let offer = serde_json::from_str::<GetTradeOfferResponse>(response)
+            .unwrap()
+            .response
+            .offer;
+
+        assert!(SupportedApps::new(&[440, 570, 730], UnsupportedAppPolicy::Skip).supports(&offer));
+        assert!(!SupportedApps::new(&[440], UnsupportedAppPolicy::Skip).supports(&offer));
+    }
+}
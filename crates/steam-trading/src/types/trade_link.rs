@@ -3,7 +3,8 @@ use std::str::FromStr;
 
 use lazy_static::lazy_static;
 use regex::Regex;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use steam_language_gen::generated::enums::{EAccountType, EUniverse};
 use steamid_parser::SteamID;
 
 use crate::errors::TradelinkError;
@@ -23,6 +24,8 @@ lazy_static! {
 /// But Steam always generates one with a token, and it is also kind of pointless to remove the token, so they are standardized to have it.
 pub struct Tradelink {
     pub link: String,
+    /// Serialized as a SteamID64 string, since it doesn't fit the numbers of some JSON parsers.
+    #[serde(with = "steam64_string")]
     pub partner_id: SteamID,
     pub token: String,
 }
@@ -36,7 +39,26 @@ impl TryFrom<String> for Tradelink {
 }
 
 impl Tradelink {
+    /// Checks that `partner_id` is an individual account on the public universe, since only those can trade.
+    pub fn validate_partner_id(partner_id: &SteamID) -> Result<(), TradelinkError> {
+        let steam64 = partner_id.to_steam64();
+        let universe = steam64 >> 56;
+        let account_type = (steam64 >> 52) & 0xF;
+        let account_number = steam64 & 0xFFFF_FFFF;
+
+        if universe != EUniverse::Public as u64
+            || account_type != EAccountType::Individual as u64
+            || account_number == 0
+        {
+            return Err(TradelinkError::InvalidPartnerId);
+        }
+
+        Ok(())
+    }
+
     pub fn validate_with_steam64(trade_link: &str, steamid: u64) -> Result<bool, TradelinkError> {
+        Self::validate_partner_id(&SteamID::from_steam64(steamid))?;
+
         let captures = TRADE_LINK_REGEX.captures(&trade_link);
 
         if captures.is_none() {
@@ -71,6 +93,14 @@ impl Tradelink {
         Ok(())
     }
 
+    /// Finds the first tradelink inside `document`, such as the trade offer privacy page.
+    pub(crate) fn find_in_document(document: &str) -> Option<Self> {
+        let document = document.replace("&amp;", "&");
+        let found = TRADE_LINK_REGEX.find(&document)?;
+
+        Self::new(found.as_str().to_string()).ok()
+    }
+
     pub fn new(trade_link: String) -> Result<Self, TradelinkError> {
         Self::validate(&*trade_link)?;
 
@@ -80,6 +110,7 @@ impl Tradelink {
             .and_then(|partner_id_raw| u32::from_str(partner_id_raw.as_str()).ok())
             .map(|partner_id| SteamID::from_steam3(partner_id, None, None))
             .ok_or_else(|| TradelinkError::Invalid)?;
+        Self::validate_partner_id(&partner_id)?;
 
         let token = captures.name("token").unwrap().as_str();
 
@@ -91,6 +122,30 @@ impl Tradelink {
     }
 }
 
+mod steam64_string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(steamid: &SteamID, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&steamid.to_steam64().to_string())
+    }
+
+    /// Also accepts the SteamID64 as a number, as it was serialized before.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SteamID, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum StringOrNumber {
+            String(String),
+            Number(u64),
+        }
+
+        let steam64 = match StringOrNumber::deserialize(deserializer)? {
+            StringOrNumber::String(steam64) => steam64.parse().map_err(serde::de::Error::custom)?,
+            StringOrNumber::Number(steam64) => steam64,
+        };
+        Ok(SteamID::from_steam64(steam64))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,6 +182,19 @@ mod tests {
         assert_eq!(result, Ok(false))
     }
 
+    #[test]
+    fn validated_with_group_steamid() {
+        let group_steamid = 103582791429521412;
+        let result = Tradelink::validate_with_steam64(get_valid_tradelink(), group_steamid);
+        assert_eq!(result, Err(TradelinkError::InvalidPartnerId));
+    }
+
+    #[test]
+    fn tradelink_with_empty_partner() {
+        let result = Tradelink::new("https://steamcommunity.com/tradeoffer/new/?partner=0&token=vnFisKdN".to_string());
+        assert_eq!(result, Err(TradelinkError::InvalidPartnerId));
+    }
+
     #[test]
     fn valid_tradelink() {
         let result = Tradelink::validate(get_valid_tradelink());
@@ -139,6 +207,17 @@ mod tests {
         assert_eq!(result, Ok(()))
     }
 
+    #[test]
+    fn tradelink_in_document() {
+        let document = r#"<input class="trade_offer_access_url" id="trade_offer_access_url" type="text" readonly
+            value="https://steamcommunity.com/tradeoffer/new/?partner=24569668&amp;token=vnFisKdN">"#;
+        let tradelink = Tradelink::find_in_document(document).unwrap();
+        assert_eq!(tradelink.token, "vnFisKdN");
+        assert_eq!(tradelink.partner_id.to_steam64(), valid_steamid());
+
+        assert_eq!(Tradelink::find_in_document(get_google()), None);
+    }
+
     #[test]
     fn invalid_tradelink() {
         let result = Tradelink::validate(get_google());
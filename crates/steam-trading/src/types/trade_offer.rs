@@ -1,12 +1,24 @@
-use std::convert::TryInto;
+use std::collections::HashSet;
+use std::convert::{TryFrom, TryInto};
 
+use serde::{Deserialize, Serialize};
+use tappet::response_types::{CEcon_Asset, TradeOffer_Trade};
 use tracing::info;
 
-use crate::errors::OfferValidationError;
+use crate::api_extensions::fingerprint;
+use crate::errors::{OfferValidationError, TradeError};
 use crate::types::asset_collection::AssetCollection;
+use crate::types::inventory::Inventory;
+use crate::types::trade_offer_web::Asset;
 use crate::{Tradelink, TRADE_MAX_ITEMS};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+/// A trade offer to be created.
+///
+/// Can be serialized, e.g. to be queued and sent later by another worker.
+///
+/// Steam expires offers two weeks after they are sent, and its create endpoint does not accept a shorter expiration.
+/// To keep offers from lingering, cancel them with `SteamTradeManager::cancel_stale_offers`.
 pub struct TradeOffer {
     /// The user who you want to trade with Steam Trade URL.
     pub their_tradelink: Tradelink,
@@ -40,30 +52,235 @@ impl TradeOffer {
         })
     }
 
-    /// Validates if at least one item is being traded or if it exceeds the 255 items limit;
+    /// Builds an offer to `tradelink` with the same items and message as `trade`, e.g. to send it again.
+    ///
+    /// Items we would give in `trade` are our assets on the new offer, whether `trade` was sent or received.
+    ///
+    /// Assets are copied by their assetid, and an assetid changes every time its item is traded. If any item moved
+    /// since `trade` was made, Steam will refuse the new offer.
+    pub fn from_trade(trade: &TradeOffer_Trade, tradelink: Tradelink) -> Result<Self, TradeError> {
+        let my_assets = trade.items_to_give.as_deref().map(asset_collection_from).transpose()?;
+        let their_assets = trade
+            .items_to_receive
+            .as_deref()
+            .map(asset_collection_from)
+            .transpose()?;
+        Self::validate(&my_assets, &their_assets)?;
+
+        Ok(Self {
+            their_tradelink: tradelink,
+            my_assets,
+            their_assets,
+            message: trade.message.clone(),
+        })
+    }
+
+    /// Returns a hash of what would be traded and with whom, to detect an identical offer already sent to the partner.
+    ///
+    /// It matches `ContentFingerprint::content_fingerprint` of the offer Steam creates from this one. Only assets added
+    /// by assetid are taken into account, so resolve the ones added by classid first.
+    pub fn request_fingerprint(&self) -> u64 {
+        let assets_of = |assets: &Option<AssetCollection>| -> Vec<(i64, i64, i64, i64)> {
+            assets
+                .iter()
+                .flat_map(|collection| collection.0.iter())
+                .map(|asset| {
+                    (
+                        asset.appid as i64,
+                        asset.contextid.parse().unwrap_or_default(),
+                        asset.assetid.parse().unwrap_or_default(),
+                        asset.amount,
+                    )
+                })
+                .collect()
+        };
+
+        fingerprint(
+            self.their_tradelink.partner_id.to_steam3(),
+            assets_of(&self.my_assets),
+            assets_of(&self.their_assets),
+        )
+    }
+
+    /// Returns a copy of this offer, with concrete assets picked for every asset added by classid.
+    ///
+    /// Our assets are picked from `my_inventory`, and the partner assets from `their_inventory`. A side without an
+    /// inventory is left untouched.
+    pub fn resolve_fungible(
+        &self,
+        my_inventory: Option<&Inventory>,
+        their_inventory: Option<&Inventory>,
+    ) -> Result<Self, OfferValidationError> {
+        let resolve = |assets: &Option<AssetCollection>, inventory: Option<&Inventory>| match (assets, inventory) {
+            (Some(assets), Some(inventory)) => assets.resolve_fungible(inventory).map(Some),
+            (assets, _) => Ok(assets.clone()),
+        };
+
+        Ok(Self {
+            their_tradelink: self.their_tradelink.clone(),
+            my_assets: resolve(&self.my_assets, my_inventory)?,
+            their_assets: resolve(&self.their_assets, their_inventory)?,
+            message: self.message.clone(),
+        })
+    }
+
+    /// Validates if at least one item is being traded, if it exceeds the 255 items limit, and that every asset is
+    /// valid and added only once.
     pub fn validate(
         my_items: &Option<AssetCollection>,
         their_items: &Option<AssetCollection>,
     ) -> Result<(), OfferValidationError> {
-        if my_items.is_none() && their_items.is_none() {
-            return Err(OfferValidationError::InvalidTrade(
-                "There can't be a trade offer with no items being traded.".to_string(),
-            ));
+        let has_unresolved_fungible = [my_items, their_items]
+            .iter()
+            .any(|items| items.as_ref().map_or(false, AssetCollection::has_unresolved_fungible));
+        if has_unresolved_fungible {
+            return Err(OfferValidationError::UnresolvedFungible);
         }
 
-        // TODO: more elegant, please
-
-        let my_length = my_items.as_ref().map(|c| c.0.len()).unwrap_or(0);
-        let their_length = their_items.as_ref().map(|c| c.0.len()).unwrap_or(0);
+        let my_length = my_items.as_ref().map(AssetCollection::item_count).unwrap_or(0);
+        let their_length = their_items.as_ref().map(AssetCollection::item_count).unwrap_or(0);
         info!("Total items being traded: My: {} Their: {}", my_length, their_length);
 
+        if my_length == 0 && their_length == 0 {
+            return Err(OfferValidationError::EmptyOffer);
+        }
         if my_length >= TRADE_MAX_ITEMS as usize || their_length >= TRADE_MAX_ITEMS as usize {
-            return Err(OfferValidationError::InvalidTrade(format!(
-                "Maximum number of items is: {}",
-                TRADE_MAX_ITEMS
-            )));
+            return Err(OfferValidationError::TooManyItems(TRADE_MAX_ITEMS));
+        }
+
+        for items in [my_items, their_items].iter().filter_map(|items| items.as_ref()) {
+            validate_assets(&items.0)?;
+            if let Some(currency) = items.2.iter().find(|currency| currency.amount <= 0) {
+                return Err(OfferValidationError::InvalidAsset(format!(
+                    "currency {} has amount {}",
+                    currency.currencyid, currency.amount
+                )));
+            }
         }
 
         Ok(())
     }
 }
+
+fn asset_collection_from(assets: &[CEcon_Asset]) -> Result<AssetCollection, OfferValidationError> {
+    let assets = assets
+        .iter()
+        .map(|asset| {
+            let appid = u32::try_from(asset.appid)
+                .map_err(|_| OfferValidationError::InvalidAsset(format!("invalid appid {}", asset.appid)))?;
+
+            Ok(Asset {
+                appid,
+                contextid: asset.contextid.to_string(),
+                amount: asset.amount,
+                assetid: asset.assetid.to_string(),
+            })
+        })
+        .collect::<Result<Vec<_>, OfferValidationError>>()?;
+
+    Ok(AssetCollection(assets, vec![], vec![]))
+}
+
+/// Checks that every asset has a valid assetid and amount, and that no asset was added twice.
+fn validate_assets(assets: &[Asset]) -> Result<(), OfferValidationError> {
+    let mut seen = HashSet::new();
+
+    for asset in assets {
+        if asset.assetid.parse::<u64>().is_err() {
+            return Err(OfferValidationError::InvalidAsset(format!(
+                "invalid assetid {}",
+                asset.assetid
+            )));
+        }
+        if asset.amount <= 0 {
+            return Err(OfferValidationError::InvalidAsset(format!(
+                "asset {} has amount {}",
+                asset.assetid, asset.amount
+            )));
+        }
+        if !seen.insert((asset.appid, &asset.contextid, &asset.assetid)) {
+            return Err(OfferValidationError::DuplicateAsset(asset.assetid.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collection_of(assetids: &[i64]) -> Option<AssetCollection> {
+        let mut collection = AssetCollection::default();
+        assetids.iter().for_each(|&assetid| collection.add(730, 2, assetid));
+        Some(collection)
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let mut my_assets = collection_of(&[15319724006]).unwrap();
+        my_assets.add_by_classid(730, 2, 1690096482, 2);
+        let offer = TradeOffer::new(
+            "https://steamcommunity.com/tradeoffer/new/?partner=79925588&token=Ob27qXzn".to_string(),
+            my_assets,
+            None,
+            "basinga".to_string(),
+        )
+        .unwrap();
+
+        let serialized = serde_json::to_string(&offer).unwrap();
+        assert!(serialized.contains(r#""partner_id":"76561198040191316""#));
+        assert_eq!(serde_json::from_str::<TradeOffer>(&serialized).unwrap(), offer);
+    }
+
+    #[test]
+    fn validate_empty_offer() {
+        assert_eq!(
+            TradeOffer::validate(&None, &None),
+            Err(OfferValidationError::EmptyOffer)
+        );
+        assert_eq!(
+            TradeOffer::validate(&collection_of(&[]), &None),
+            Err(OfferValidationError::EmptyOffer)
+        );
+    }
+
+    #[test]
+    fn validate_too_many_items() {
+        let assetids = (0..TRADE_MAX_ITEMS as i64).collect::<Vec<_>>();
+        assert_eq!(
+            TradeOffer::validate(&collection_of(&assetids), &None),
+            Err(OfferValidationError::TooManyItems(TRADE_MAX_ITEMS))
+        );
+    }
+
+    #[test]
+    fn validate_unresolved_fungible() {
+        let mut collection = AssetCollection::default();
+        collection.add_by_classid(730, 2, 1690096482, 1);
+        assert_eq!(
+            TradeOffer::validate(&Some(collection), &None),
+            Err(OfferValidationError::UnresolvedFungible)
+        );
+    }
+
+    #[test]
+    fn validate_duplicate_asset() {
+        assert_eq!(
+            TradeOffer::validate(&collection_of(&[15319724006, 15319724006]), &None),
+            Err(OfferValidationError::DuplicateAsset("15319724006".to_string()))
+        );
+        // the same assetid on different sides belongs to different accounts
+        assert!(TradeOffer::validate(&collection_of(&[15319724006]), &collection_of(&[15319724006])).is_ok());
+    }
+
+    #[test]
+    fn validate_invalid_asset() {
+        let mut collection = collection_of(&[15319724006]).unwrap();
+        collection.0[0].amount = 0;
+        assert!(matches!(
+            TradeOffer::validate(&Some(collection), &None),
+            Err(OfferValidationError::InvalidAsset(_))
+        ));
+    }
+}
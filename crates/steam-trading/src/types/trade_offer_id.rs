@@ -0,0 +1,80 @@
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Id of a trade offer.
+///
+/// Steam sends trade offer ids as strings in most JSON responses, but as numbers in a few others. This type
+/// deserializes from both, and can be parsed with `FromStr`, so there is no need to parse them manually.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TradeOfferId(pub i64);
+
+impl FromStr for TradeOfferId {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        i64::from_str(s.trim()).map(Self)
+    }
+}
+
+impl From<i64> for TradeOfferId {
+    fn from(tradeofferid: i64) -> Self {
+        Self(tradeofferid)
+    }
+}
+
+impl From<TradeOfferId> for i64 {
+    fn from(tradeofferid: TradeOfferId) -> Self {
+        tradeofferid.0
+    }
+}
+
+impl fmt::Display for TradeOfferId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for TradeOfferId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TradeOfferId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum StringOrNumber {
+            String(String),
+            Number(i64),
+        }
+
+        match StringOrNumber::deserialize(deserializer)? {
+            StringOrNumber::String(tradeofferid) => tradeofferid.parse().map_err(D::Error::custom),
+            StringOrNumber::Number(tradeofferid) => Ok(Self(tradeofferid)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_from_str() {
+        assert_eq!("4112828817".parse::<TradeOfferId>(), Ok(TradeOfferId(4112828817)));
+        assert!("not an id".parse::<TradeOfferId>().is_err());
+    }
+
+    #[test]
+    fn deserialize_string_or_number() {
+        let from_string = serde_json::from_str::<TradeOfferId>(r#""4112828817""#).unwrap();
+        let from_number = serde_json::from_str::<TradeOfferId>("4112828817").unwrap();
+        assert_eq!(from_string, from_number);
+        assert_eq!(serde_json::to_string(&from_number).unwrap(), r#""4112828817""#);
+    }
+}
@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 use steam_language_gen::generated::enums::EResult;
 
+use crate::errors::{
+    error_from_strmessage, is_session_error_strmessage, tradelink_error_from_strmessage, tradeoffer_error_from_eresult,
+};
 use crate::types::sessionid::{HasSessionID, SessionID};
-use crate::{AssetCollection, TradeOffer};
+use crate::{AssetCollection, OfferError, TradeError, TradeOffer};
 
 macro_rules! impl_sessionid {
     ($name:ident) => {
@@ -15,7 +18,7 @@ macro_rules! impl_sessionid {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub(crate) struct TradeOfferCommonParameters {
+pub struct TradeOfferCommonParameters {
     pub serverid: i32,
     #[serde(rename = "partner")]
     /// Recipient STEAMID64. Ex: 76561198040191316
@@ -42,13 +45,46 @@ pub(crate) struct TradeOfferGenericRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
+/// Steam reports failures in different shapes depending on the endpoint: `strError` or `error_message` carry a
+/// message, usually with the EResult between parenthesis, while `success` carries the EResult alone.
 pub(crate) struct TradeOfferGenericErrorResponse {
     #[serde(rename = "success")]
     pub eresult: Option<EResult>,
     #[serde(rename = "strError")]
+    pub str_error: Option<String>,
     pub error_message: Option<String>,
 }
 
+impl TradeOfferGenericErrorResponse {
+    /// Maps the response into a typed error, or `None` if it carries no error at all.
+    ///
+    /// Problems with our own session map into `TradeError::SessionExpired`, and are told apart from problems with the
+    /// partner's tradelink, that map into `TradelinkError`.
+    pub(crate) fn into_error(self) -> Option<TradeError> {
+        // some endpoints answer with `"success": 1` when there is nothing wrong
+        let eresult = self.eresult.filter(|&eresult| eresult != EResult::OK);
+        if eresult == Some(EResult::NotLoggedOn) {
+            return Some(TradeError::SessionExpired);
+        }
+
+        if let Some(message) = self.str_error.or(self.error_message) {
+            if is_session_error_strmessage(&*message) {
+                return Some(TradeError::SessionExpired);
+            }
+            if let Some(tradelink_error) = tradelink_error_from_strmessage(&*message) {
+                return Some(tradelink_error.into());
+            }
+
+            let error = error_from_strmessage(&*message)
+                .or_else(|| eresult.map(tradeoffer_error_from_eresult))
+                .unwrap_or(OfferError::GeneralFailure(message));
+            return Some(error.into());
+        }
+
+        eresult.map(|eresult| tradeoffer_error_from_eresult(eresult).into())
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// Url: https://steamcommunity.com/tradeoffer/4127395150/accept
 pub(crate) struct TradeOfferAcceptRequest {
@@ -70,6 +106,14 @@ pub struct TradeOfferCreateResponse {
     pub needs_mobile_confirmation: Option<bool>,
     pub needs_email_confirmation: Option<bool>,
     pub email_domain: Option<String>,
+    /// Items from our inventory, as Steam accepted them into the offer.
+    ///
+    /// Only present if Steam echoes them back. Compare with what was sent to make sure no asset was dropped.
+    pub items_to_give: Option<Vec<Asset>>,
+    /// Items from the partner inventory, as Steam accepted them into the offer.
+    pub items_to_receive: Option<Vec<Asset>>,
+    /// Unix epoch when the items of the offer are released, if Steam holds them in escrow. Zero if not held.
+    pub escrow_end_date: Option<i64>,
 }
 
 /// Response after we cancel an trade offer we've sent.
@@ -82,7 +126,7 @@ pub struct TradeOfferCancelResponse {
 /// We create a trade offer from a Steam Trade link the user shares with us.
 /// The "partner" number, is the SteamID3. In order to send the trade offer, first we need to to
 /// convert it to a SteamID64.
-pub(crate) struct TradeOfferCreateRequest {
+pub struct TradeOfferCreateRequest {
     /// Session ID cookie from Steam Community.
     #[serde(flatten)]
     pub sessionid: SessionID,
@@ -101,7 +145,11 @@ pub(crate) struct TradeOfferCreateRequest {
 }
 
 impl TradeOfferCreateRequest {
-    pub(crate) fn new<T: Into<Option<TradeOfferParams>>>(
+    /// Builds the request Steam expects for `tradeoffer`, sent to `their_steamid64`.
+    ///
+    /// Unlike `SteamTradeManager::create_offer`, the offer is not validated. The request can be changed further before
+    /// sending it with `SteamTradeManager::create_offer_raw`.
+    pub fn new<T: Into<Option<TradeOfferParams>>>(
         their_steamid64: u64,
         tradeoffer: TradeOffer,
         trade_token: T,
@@ -120,7 +168,7 @@ impl TradeOfferCreateRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub(crate) struct TradeOfferParams {
+pub struct TradeOfferParams {
     /// A trade offer link has an unique token that the user can invalidate at any time.
     /// We need to insert this token correct at the request.
     pub trade_offer_access_token: String,
@@ -129,17 +177,18 @@ pub(crate) struct TradeOfferParams {
 impl Default for TradeOfferCreateRequest {
     fn default() -> Self {
         Self {
+            sessionid: Default::default(),
+            common: Default::default(),
             message: "".to_string(),
             json_tradeoffer: Default::default(),
             trade_offer_create_params: None,
-            ..Default::default()
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// Trade offer format to be sent through Steam.
-pub(crate) struct JsonTradeOffer {
+pub struct JsonTradeOffer {
     pub newversion: bool,
     pub version: i32,
     #[serde(rename = "me")]
@@ -181,9 +230,9 @@ impl Default for JsonTradeOffer {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// The correct format for assets inside the trade offer.
-pub(crate) struct AssetList {
+pub struct AssetList {
     pub assets: Vec<Asset>,
-    pub currency: Vec<String>,
+    pub currency: Vec<CurrencyAsset>,
     pub ready: bool,
 }
 
@@ -206,11 +255,26 @@ pub struct Asset {
     // u32
     pub contextid: String,
     /// Amount if the item is stackable.
-    pub(crate) amount: i64,
+    pub amount: i64,
     // u64
     pub assetid: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Amount of a Steam currency item, such as the wallet-like currencies of some apps, that are traded by amount instead
+/// of by assetid.
+pub struct CurrencyAsset {
+    /// Game APPID.
+    pub appid: u32,
+    /// Inventory ContextID.
+    // u32
+    pub contextid: String,
+    // u64
+    pub currencyid: String,
+    /// Amount of the currency traded.
+    pub amount: i64,
+}
+
 impl_sessionid!(TradeOfferGenericRequest);
 impl_sessionid!(TradeOfferAcceptRequest);
 impl_sessionid!(TradeOfferCreateRequest);
@@ -218,6 +282,73 @@ impl_sessionid!(TradeOfferCreateRequest);
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::TradelinkError;
+
+    fn generic_error(response: &str) -> Option<TradeError> {
+        serde_json::from_str::<TradeOfferGenericErrorResponse>(response)
+            .unwrap()
+            .into_error()
+    }
+
+    #[test]
+    fn generic_error_str_error() {
+        let error = generic_error(r#"{"strError":"There was an error accepting this trade offer. (11)"}"#);
+        assert!(matches!(
+            error,
+            Some(TradeError::TradeOfferError(OfferError::InvalidState))
+        ));
+
+        let error = generic_error(r#"{"strError":"This Trade URL is no longer valid for sending a trade offer (15)"}"#);
+        assert!(matches!(
+            error,
+            Some(TradeError::TradelinkError(TradelinkError::TokenExpired))
+        ));
+    }
+
+    #[test]
+    fn generic_error_session_expired() {
+        let error = generic_error(r#"{"strError":"You must be logged in to perform this action. (21)"}"#);
+        assert!(matches!(error, Some(TradeError::SessionExpired)));
+
+        let error = generic_error(r#"{"success":21}"#);
+        assert!(matches!(error, Some(TradeError::SessionExpired)));
+    }
+
+    #[test]
+    fn generic_error_error_message() {
+        let error = generic_error(r#"{"error_message":"Something went wrong (26)"}"#);
+        assert!(matches!(error, Some(TradeError::TradeOfferError(OfferError::Revoked))));
+
+        let error = generic_error(r#"{"error_message":"Something went wrong","success":42}"#);
+        assert!(matches!(error, Some(TradeError::TradeOfferError(OfferError::NoMatch))));
+
+        let error = generic_error(r#"{"error_message":"Something went wrong"}"#);
+        assert!(matches!(
+            error,
+            Some(TradeError::TradeOfferError(OfferError::GeneralFailure(_)))
+        ));
+    }
+
+    #[test]
+    fn generic_error_partner_trade_banned() {
+        let error = generic_error(
+            r#"{"strError":"There was an error accepting this trade offer.  Please try again later.<br><br>You cannot trade with Basinga because they have a trade ban. (15)"}"#,
+        );
+        assert!(matches!(
+            error,
+            Some(TradeError::TradeOfferError(OfferError::PartnerTradeBanned))
+        ));
+    }
+
+    #[test]
+    fn generic_error_eresult() {
+        let error = generic_error(r#"{"success":11}"#);
+        assert!(matches!(
+            error,
+            Some(TradeError::TradeOfferError(OfferError::InvalidState))
+        ));
+        assert!(generic_error("{}").is_none());
+    }
 
     fn get_offer() -> JsonTradeOffer {
         let json_request = r#"{
@@ -256,4 +387,95 @@ mod tests {
 }"#;
         serde_json::from_str::<JsonTradeOffer>(json_request).unwrap()
     }
+
+    fn get_create_response_with_items() -> &'static str {
+        r#"{
+  "tradeofferid":"4112828817",
+  "needs_mobile_confirmation":true,
+  "needs_email_confirmation":false,
+  "email_domain":"gmail.com",
+  "items_to_give":[
+    {
+      "appid":730,
+      "contextid":"2",
+      "amount":1,
+      "assetid":"17034419698"
+    }
+  ],
+  "items_to_receive":[
+    {
+      "appid":730,
+      "contextid":"2",
+      "amount":1,
+      "assetid":"18116227588"
+    }
+  ]
+}"#
+    }
+
+    #[test]
+    fn create_response_echoed_items() {
+        let response = serde_json::from_str::<TradeOfferCreateResponse>(get_create_response_with_items()).unwrap();
+        let sent = get_offer();
+
+        assert_eq!(response.items_to_give.unwrap()[0], sent.my_account.assets[0]);
+        assert_eq!(response.items_to_receive.unwrap(), sent.their_account.assets);
+    }
+
+    #[test]
+    fn json_tradeoffer_shape() {
+        let mut my_assets = AssetCollection::default();
+        my_assets.add(730, 2, 17034419698);
+        my_assets.add_currency(753, 3, 1, 250);
+        let mut their_assets = AssetCollection::default();
+        their_assets.add(730, 2, 18116227588);
+
+        let offer = TradeOffer::new(
+            "https://steamcommunity.com/tradeoffer/new/?partner=79925588&token=Ob27qXzn".to_string(),
+            my_assets,
+            their_assets,
+            "".to_string(),
+        )
+        .unwrap();
+
+        let json_tradeoffer = serde_json::to_value(JsonTradeOffer::from(offer)).unwrap();
+        assert_eq!(
+            json_tradeoffer,
+            serde_json::json!({
+                "newversion": true,
+                "version": 2,
+                "me": {
+                    "assets": [{ "appid": 730, "contextid": "2", "amount": 1, "assetid": "17034419698" }],
+                    "currency": [{ "appid": 753, "contextid": "3", "currencyid": "1", "amount": 250 }],
+                    "ready": false
+                },
+                "them": {
+                    "assets": [{ "appid": 730, "contextid": "2", "amount": 1, "assetid": "18116227588" }],
+                    "currency": [],
+                    "ready": false
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn create_response_without_items() {
+        let response = serde_json::from_str::<TradeOfferCreateResponse>(r#"{"tradeofferid":"4112828817"}"#).unwrap();
+        assert_eq!(response.items_to_give, None);
+        assert_eq!(response.items_to_receive, None);
+    }
+
+    #[test]
+    fn raw_create_request_form() {
+        let mut request = TradeOfferCreateRequest::default();
+        request.common.their_steamid = 76561198040191316;
+        request.json_tradeoffer.version = 4;
+        request.set_sessionid("abc".to_string());
+
+        let form = serde_json::to_value(&request).unwrap();
+        assert_eq!(form["sessionid"], "abc");
+        assert_eq!(form["partner"], 76561198040191316u64);
+        assert_eq!(form["serverid"], 1);
+        assert!(form["json_tradeoffer"].as_str().unwrap().contains(r#""version":4"#));
+    }
 }
@@ -0,0 +1,90 @@
+use crate::MAX_HISTORICAL_CUTOFF;
+
+/// Which offers GetTradeOffers should return, and how much of them.
+///
+/// Accounts with many offers get large responses, so ask only for what is needed. By default, only active offers are
+/// returned, without item descriptions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeOffersQuery {
+    pub(crate) sent: bool,
+    pub(crate) received: bool,
+    pub(crate) active_only: bool,
+    pub(crate) historical_only: bool,
+    pub(crate) time_historical_cutoff: u32,
+    pub(crate) language: Option<String>,
+}
+
+impl TradeOffersQuery {
+    /// Offers sent by this account.
+    pub fn sent() -> Self {
+        Self::new(true, false)
+    }
+
+    /// Offers received by this account.
+    pub fn received() -> Self {
+        Self::new(false, true)
+    }
+
+    /// Offers both sent and received by this account.
+    pub fn all() -> Self {
+        Self::new(true, true)
+    }
+
+    fn new(sent: bool, received: bool) -> Self {
+        Self {
+            sent,
+            received,
+            active_only: true,
+            historical_only: false,
+            time_historical_cutoff: MAX_HISTORICAL_CUTOFF,
+            language: None,
+        }
+    }
+
+    /// Also returns offers that are no longer active, such as accepted or declined ones.
+    pub fn with_historical(mut self) -> Self {
+        self.active_only = false;
+        self
+    }
+
+    /// Only returns offers that are no longer active.
+    pub fn historical_only(mut self) -> Self {
+        self.active_only = false;
+        self.historical_only = true;
+        self
+    }
+
+    /// Only returns offers that are no longer active if they were last updated after `cutoff`, an unix epoch.
+    pub fn with_historical_cutoff(mut self, cutoff: u32) -> Self {
+        self.time_historical_cutoff = cutoff;
+        self
+    }
+
+    /// Also returns the descriptions of the items of the offers, in `language`, such as "english".
+    pub fn with_descriptions<T: Into<String>>(mut self, language: T) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_builder() {
+        let query = TradeOffersQuery::received();
+        assert!(!query.sent && query.received);
+        assert!(query.active_only && !query.historical_only);
+        assert_eq!(query.language, None);
+
+        let query = TradeOffersQuery::all()
+            .historical_only()
+            .with_historical_cutoff(1603997472)
+            .with_descriptions("english");
+        assert!(query.sent && query.received);
+        assert!(!query.active_only && query.historical_only);
+        assert_eq!(query.time_historical_cutoff, 1603997472);
+        assert_eq!(query.language.as_deref(), Some("english"));
+    }
+}
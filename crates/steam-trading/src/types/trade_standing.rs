@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use tappet::response_types::PlayerBans;
+
+const ECONOMY_BAN_PROBATION: &str = "probation";
+const ECONOMY_BAN_BANNED: &str = "banned";
+
+/// Bans of an account that may be relevant before trading with it, from the GetPlayerBans endpoint.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeStanding {
+    pub community_banned: bool,
+    /// Account is banned from trading.
+    pub trade_banned: bool,
+    /// Account was recently trade banned, and is still being watched.
+    pub on_probation: bool,
+    pub vac_banned: bool,
+    pub number_of_vac_bans: u32,
+    pub number_of_game_bans: u32,
+    pub days_since_last_ban: u32,
+}
+
+impl TradeStanding {
+    /// True if the account is restricted in the community or trading, and probably should not be traded with.
+    pub fn is_restricted(&self) -> bool {
+        self.community_banned || self.trade_banned || self.on_probation
+    }
+}
+
+impl From<PlayerBans> for TradeStanding {
+    fn from(bans: PlayerBans) -> Self {
+        Self {
+            community_banned: bans.community_banned,
+            trade_banned: bans.economy_ban == ECONOMY_BAN_BANNED,
+            on_probation: bans.economy_ban == ECONOMY_BAN_PROBATION,
+            vac_banned: bans.vacbanned,
+            number_of_vac_bans: bans.number_of_vac_bans.max(0) as u32,
+            number_of_game_bans: bans.number_of_game_bans.max(0) as u32,
+            days_since_last_ban: bans.days_since_last_ban.max(0) as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player_bans(economy_ban: &str) -> PlayerBans {
+        let response = format!(
+            r#"{{
+  "SteamId": "76561198040191316",
+  "CommunityBanned": false,
+  "VACBanned": true,
+  "NumberOfVACBans": 1,
+  "DaysSinceLastBan": 1337,
+  "NumberOfGameBans": 0,
+  "EconomyBan": "{}"
+}}"#,
+            economy_ban
+        );
+        serde_json::from_str::<PlayerBans>(&response).unwrap()
+    }
+
+    #[test]
+    fn standing_from_bans() {
+        let standing = TradeStanding::from(player_bans("none"));
+        assert!(standing.vac_banned);
+        assert_eq!(standing.days_since_last_ban, 1337);
+        assert!(!standing.is_restricted());
+
+        let standing = TradeStanding::from(player_bans("probation"));
+        assert!(standing.on_probation && !standing.trade_banned);
+        assert!(standing.is_restricted());
+
+        assert!(TradeStanding::from(player_bans("banned")).trade_banned);
+    }
+}
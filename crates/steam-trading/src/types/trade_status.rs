@@ -0,0 +1,84 @@
+use tappet::response_types::{ETradeStatus, TradeHistory_Trade};
+
+/// Settlement of a single completed trade, from the GetTradeStatus endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeStatus {
+    pub tradeid: i64,
+    pub status: ETradeStatus,
+    /// Unix epoch when the received items leave escrow, if the trade was held.
+    pub escrow_end: Option<i64>,
+    /// Asset ids of the received items, after the trade.
+    pub new_assetids: Vec<i64>,
+}
+
+impl From<TradeHistory_Trade> for TradeStatus {
+    fn from(trade: TradeHistory_Trade) -> Self {
+        Self {
+            tradeid: trade.tradeid,
+            status: trade.status,
+            escrow_end: trade.time_escrow_end.filter(|&end| end > 0),
+            new_assetids: trade
+                .assets_received
+                .unwrap_or_default()
+                .into_iter()
+                .map(|asset| asset.new_assetid)
+                .collect(),
+        }
+    }
+}
+
+impl TradeStatus {
+    /// True while Steam is still exchanging the items of the trade.
+    pub fn is_in_progress(&self) -> bool {
+        matches!(
+            self.status,
+            ETradeStatus::Init | ETradeStatus::PreCommitted | ETradeStatus::Committed
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tappet::response_types::GetTradeStatusResponse;
+
+    use super::*;
+
+    #[test]
+    fn status_from_response() {
+        let response = r#"{
+  "response": {
+    "trades": [
+      {
+        "tradeid": "3151905948742966439",
+        "steamid_other": "76561198040191316",
+        "time_init": 1594190957,
+        "time_escrow_end": 1594795757,
+        "status": 3,
+        "assets_received": [
+          {
+            "appid": 730,
+            "contextid": "2",
+            "assetid": "17300115678",
+            "amount": "1",
+            "classid": "1989330488",
+            "instanceid": "302028390",
+            "new_assetid": "19034292089",
+            "new_contextid": "2"
+          }
+        ]
+      }
+    ]
+  }
+}"#;
+        let mut response = serde_json::from_str::<GetTradeStatusResponse>(response).unwrap();
+        let status = TradeStatus::from(response.response.trades.remove(0));
+        assert_eq!(status.tradeid, 3151905948742966439);
+        assert_eq!(status.status, ETradeStatus::Complete);
+        assert_eq!(status.escrow_end, Some(1594795757));
+        assert_eq!(status.new_assetids, vec![19034292089]);
+        assert!(!status.is_in_progress());
+
+        let response = serde_json::from_str::<GetTradeStatusResponse>(r#"{"response": {}}"#).unwrap();
+        assert!(response.response.trades.is_empty());
+    }
+}
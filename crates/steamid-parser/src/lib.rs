@@ -1,3 +1,4 @@
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
 use bitvec::prelude::*;
@@ -153,6 +154,15 @@ impl SteamID {
     }
 }
 
+impl Eq for SteamID {}
+
+/// Hashed by its SteamID64, so it can key maps.
+impl Hash for SteamID {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_steam64().hash(state);
+    }
+}
+
 #[cfg(feature = "serialize")]
 impl Serialize for SteamID {
     fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
@@ -84,6 +84,7 @@ pub fn derive_parameters(input: TokenStream) -> TokenStream {
         #[cfg(feature = "async")]
         pub struct #new_ident<'a> {
             pub(crate) key: &'a str,
+            pub(crate) credential: crate::CredentialKind,
             pub(crate) request: reqwest::Request,
             pub(crate) client: &'a reqwest::Client,
             pub(crate) parameters: #struct_parameters_name,
@@ -93,6 +94,7 @@ pub fn derive_parameters(input: TokenStream) -> TokenStream {
         #[cfg(feature = "blocking")]
         pub struct #new_ident<'a> {
             pub(crate) key: &'a str,
+            pub(crate) credential: crate::CredentialKind,
             pub(crate) request: reqwest::blocking::Request,
             pub(crate) client: &'a reqwest::blocking::Client,
             pub(crate) parameters: #struct_parameters_name,
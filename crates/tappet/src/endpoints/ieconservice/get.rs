@@ -1,6 +1,6 @@
 use tappet_derive::{interface, Parameters};
 
-use crate::response_types::GetTradeHoldDurationsResponseBase;
+use crate::response_types::{GetTradeHoldDurationsResponseBase, GetTradeOffersSummaryResponseBase};
 
 import!();
 
@@ -52,22 +52,43 @@ pub struct GetTradeOfferParameters {
     language: Option<String>,
 }
 
+#[interface(IEconService)]
+#[derive(Parameters, Serialize, Debug, Default)]
+#[doc(hidden)]
+pub struct GetTradeOffersSummaryParameters {
+    time_last_visit: u32,
+}
+
+#[interface(IEconService)]
+#[derive(Parameters, Serialize, Debug, Default)]
+#[doc(hidden)]
+pub struct GetTradeStatusParameters {
+    tradeid: i64,
+    get_descriptions: Option<bool>,
+    language: Option<String>,
+}
+
 convert_with_endpoint!(@IEconService -> GetTradeHistory |> "GetTradeHistory/v1");
 convert_with_endpoint!(@IEconService -> GetTradeOffers |> "GetTradeOffers/v1");
 convert_with_endpoint!(@IEconService -> GetTradeOffer |> "GetTradeOffer/v1");
 convert_with_endpoint!(@IEconService -> GetTradeHoldDurations |> "GetTradeHoldDurations/v1");
+convert_with_endpoint!(@IEconService -> GetTradeStatus |> "GetTradeStatus/v1");
+convert_with_endpoint!(@IEconService -> GetTradeOffersSummary |> "GetTradeOffersSummary/v1");
 
 impl_executor!(GetTradeHoldDurations -> GetTradeHoldDurationsResponseBase);
+impl_executor!(GetTradeOffersSummary -> GetTradeOffersSummaryResponseBase);
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "trading")] {
-        use crate::response_types::{ GetTradeHistoryResponse, GetTradeOffersResponse, GetTradeOfferResponse };
+        use crate::response_types::{ GetTradeHistoryResponse, GetTradeOffersResponse, GetTradeOfferResponse, GetTradeStatusResponse };
         impl_executor!(GetTradeHistory -> GetTradeHistoryResponse);
         impl_executor!(GetTradeOffers -> GetTradeOffersResponse);
         impl_executor!(GetTradeOffer -> GetTradeOfferResponse);
+        impl_executor!(GetTradeStatus -> GetTradeStatusResponse);
     } else {
         impl_executor!(GetTradeHistory);
         impl_executor!(GetTradeOffers);
         impl_executor!(GetTradeOffer);
+        impl_executor!(GetTradeStatus);
     }
 }
@@ -89,11 +89,31 @@ mod trading_types;
 
 pub type Result<T> = std::result::Result<T, SteamAPIError>;
 
+/// How requests are authenticated with the Steam Web API.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CredentialKind {
+    /// Legacy Web API key, sent as the `key` parameter.
+    ApiKey,
+    /// Access token of a logged in session, sent as the `access_token` parameter.
+    ///
+    /// Steam is moving away from Web API keys in favour of access tokens.
+    AccessToken,
+}
+
+impl CredentialKind {
+    pub(crate) fn parameter_name(self) -> &'static str {
+        match self {
+            CredentialKind::ApiKey => "key",
+            CredentialKind::AccessToken => "access_token",
+        }
+    }
+}
+
 #[cfg(feature = "blocking")]
 pub mod blocking {
     use serde::de::DeserializeOwned;
 
-    use crate::Result;
+    use crate::{CredentialKind, Result};
 
     /// Requests the endpoint and returns the raw response.
     pub trait Executor {
@@ -111,6 +131,7 @@ pub mod blocking {
         pub(crate) client: reqwest::blocking::Client,
         /// Mandatory for some operations
         pub(crate) key: String,
+        pub(crate) credential: CredentialKind,
     }
 
     impl SteamAPI {
@@ -119,11 +140,30 @@ pub mod blocking {
             Self {
                 client: Default::default(),
                 key: api_key.to_string(),
+                credential: CredentialKind::ApiKey,
+            }
+        }
+
+        /// Creates a new SteamAPI Client authenticated with an access token instead of an API Key.
+        pub fn with_access_token<T: ToString>(access_token: T) -> SteamAPI {
+            Self {
+                client: Default::default(),
+                key: access_token.to_string(),
+                credential: CredentialKind::AccessToken,
             }
         }
 
         pub fn set_api_key<T: ToString>(&mut self, api_key: T) {
             self.key = api_key.to_string();
+            self.credential = CredentialKind::ApiKey;
+        }
+
+        /// Routes every request through the proxy at `proxy_url`, e.g. "socks5://127.0.0.1:1080".
+        pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self> {
+            self.client = reqwest::blocking::Client::builder()
+                .proxy(reqwest::Proxy::all(proxy_url)?)
+                .build()?;
+            Ok(self)
         }
 
         pub fn get(&self) -> GetQueryBuilder {
@@ -143,12 +183,16 @@ mod async_client {
     use async_trait::async_trait;
     use serde::de::DeserializeOwned;
 
-    use crate::Result;
+    use crate::{CredentialKind, Result};
 
     #[async_trait]
     /// Requests the endpoint and returns the raw response.
     pub trait Executor {
         async fn execute(self) -> Result<String>;
+
+        /// Requests the endpoint and returns the response before its body is read, so it can be read in chunks with
+        /// `reqwest::Response::chunk`, instead of being held in memory as a whole.
+        async fn execute_raw(self) -> Result<reqwest::Response>;
     }
 
     #[async_trait]
@@ -163,6 +207,7 @@ mod async_client {
         pub(crate) client: reqwest::Client,
         /// Mandatory for some operations
         pub(crate) key: String,
+        pub(crate) credential: CredentialKind,
     }
 
     impl SteamAPI {
@@ -171,11 +216,30 @@ mod async_client {
             Self {
                 client: Default::default(),
                 key: api_key.to_string(),
+                credential: CredentialKind::ApiKey,
+            }
+        }
+
+        /// Creates a new SteamAPI Client authenticated with an access token instead of an API Key.
+        pub fn with_access_token<T: ToString>(access_token: T) -> SteamAPI {
+            Self {
+                client: Default::default(),
+                key: access_token.to_string(),
+                credential: CredentialKind::AccessToken,
             }
         }
 
         pub fn set_api_key<T: ToString>(&mut self, api_key: T) {
             self.key = api_key.to_string();
+            self.credential = CredentialKind::ApiKey;
+        }
+
+        /// Routes every request through the proxy at `proxy_url`, e.g. "socks5://127.0.0.1:1080".
+        pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self> {
+            self.client = reqwest::Client::builder()
+                .proxy(reqwest::Proxy::all(proxy_url)?)
+                .build()?;
+            Ok(self)
         }
 
         pub fn get(&self) -> GetQueryBuilder {
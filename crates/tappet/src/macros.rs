@@ -4,11 +4,29 @@ use async_trait::async_trait;
 use futures::future;
 
 #[derive(serde::Serialize, Debug)]
-/// We use this struct to wrap this endpoint parameters struct, plus our API Key
+/// We use this struct to wrap this endpoint parameters struct, plus our API Key or access token
 pub(crate) struct FormWrapper<'a, T: serde::Serialize> {
     #[serde(flatten)]
     pub(crate) parameters: T,
-    pub(crate) key: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) key: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) access_token: Option<&'a str>,
+}
+
+impl<'a, T: serde::Serialize> FormWrapper<'a, T> {
+    pub(crate) fn new(parameters: T, credential: crate::CredentialKind, key: &'a str) -> Self {
+        let (key, access_token) = match credential {
+            crate::CredentialKind::ApiKey => (Some(key), None),
+            crate::CredentialKind::AccessToken => (None, Some(key)),
+        };
+
+        Self {
+            parameters,
+            key,
+            access_token,
+        }
+    }
 }
 
 #[cfg(feature = "async")]
@@ -57,6 +75,7 @@ macro_rules! from {
                 Self {
                     client: &api.client,
                     key: &*api.key,
+                    credential: api.credential,
                     request,
                 }
             }
@@ -71,6 +90,7 @@ macro_rules! new_type {
             pub(crate) request: reqwest::blocking::Request,
             pub(crate) client: &'a reqwest::blocking::Client,
             pub(crate) key: &'a str,
+            pub(crate) credential: crate::CredentialKind,
         }
 
         #[cfg(feature = "async")]
@@ -78,6 +98,7 @@ macro_rules! new_type {
             pub(crate) request: reqwest::Request,
             pub(crate) client: &'a reqwest::Client,
             pub(crate) key: &'a str,
+            pub(crate) credential: crate::CredentialKind,
         }
     };
 }
@@ -90,7 +111,7 @@ macro_rules! impl_executor {
                 use paste::paste;
 
                 let query: String = self.recover_params();
-                let api_key_parameter = format!("key={}", self.key);
+                let api_key_parameter = format!("{}={}", self.credential.parameter_name(), self.key);
                 let mut req = self.request;
                 let url = req.url_mut();
                 url.set_query(Some(&(api_key_parameter + "&" + &query)));
@@ -110,7 +131,7 @@ macro_rules! impl_executor {
                 use futures::future::TryFutureExt;
 
                 let query: String = self.recover_params();
-                let api_key_parameter = format!("key={}", self.key);
+                let api_key_parameter = format!("{}={}", self.credential.parameter_name(), self.key);
                 let mut req = self.request;
                 let url = req.url_mut();
                 url.set_query(Some(&(api_key_parameter + "&" + &query)));
@@ -131,7 +152,7 @@ macro_rules! impl_executor {
         impl<'a> Executor for $base<'a> {
             fn execute(self) -> crate::Result<String> {
                 let query: String = self.recover_params();
-                let api_key_parameter = format!("key={}", self.key);
+                let api_key_parameter = format!("{}={}", self.credential.parameter_name(), self.key);
                 let mut req = self.request;
                 let url = req.url_mut();
                 url.set_query(Some(&(api_key_parameter + "&" + &query)));
@@ -144,12 +165,14 @@ macro_rules! impl_executor {
         #[cfg(feature = "async")]
         impl<'a> Executor for $base<'a> {
             async fn execute(self) -> crate::Result<String> {
-                use futures::future::TryFutureExt;
+                self.execute_raw().await?.text().await.map_err(|e| e.into())
+            }
 
+            async fn execute_raw(self) -> crate::Result<reqwest::Response> {
                 match self.request.method() {
                     &reqwest::Method::GET => {
                         let query: String = self.recover_params();
-                        let api_key_parameter = format!("key={}", self.key);
+                        let api_key_parameter = format!("{}={}", self.credential.parameter_name(), self.key);
                         let mut req = self.request;
                         let url = req.url_mut();
                         url.set_query(Some(&(api_key_parameter + "&" + &query)));
@@ -158,24 +181,21 @@ macro_rules! impl_executor {
                         let headers = response.headers();
                         let status_code = response.status();
                         headers_error_check(status_code, headers)?;
-                        response.text().await.map_err(|e| e.into())
+                        Ok(response)
                     }
 
                     &reqwest::Method::POST => {
                         let data = self.recover_params_as_form();
                         let url = self.request.url().to_owned();
 
-                        let data = crate::macros::FormWrapper {
-                            parameters: data,
-                            key: self.key,
-                        };
+                        let data = crate::macros::FormWrapper::new(data, self.credential, self.key);
 
                         let new_req = self.client.post(url).form(&data).build().unwrap();
                         let response = self.client.execute(new_req).await?;
                         let headers = response.headers();
                         let status_code = response.status();
                         headers_error_check(status_code, headers)?;
-                        response.text().await.map_err(|e| e.into())
+                        Ok(response)
                     }
                     _ => unimplemented!(),
                 }
@@ -223,6 +243,7 @@ macro_rules! convert_with_endpoint {
                 Self {
                     request,
                     key: api.key,
+                    credential: api.credential,
                     client: api.client,
                 }
             }
@@ -239,6 +260,7 @@ macro_rules! convert_with_endpoint {
                 Self {
                     client: api.client,
                     key: api.key,
+                    credential: api.credential,
                     parameters: Default::default(),
                     request,
                 }
@@ -118,6 +118,38 @@ pub struct GetTradeHoldDurations {
     pub both_escrow: Option<EscrowData>,
 }
 
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+/// Base response for GetTradeOffersSummary
+pub struct GetTradeOffersSummaryResponseBase {
+    pub response: GetTradeOffersSummary,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+/// Counts of trade offers, sent and received.
+/// "New" and "updated" counts are relative to `time_last_visit` of the request.
+pub struct GetTradeOffersSummary {
+    #[serde(default)]
+    pub pending_received_count: u32,
+    #[serde(default)]
+    pub new_received_count: u32,
+    #[serde(default)]
+    pub updated_received_count: u32,
+    #[serde(default)]
+    pub historical_received_count: u32,
+    #[serde(default)]
+    pub pending_sent_count: u32,
+    #[serde(default)]
+    pub newly_accepted_sent_count: u32,
+    #[serde(default)]
+    pub updated_sent_count: u32,
+    #[serde(default)]
+    pub historical_sent_count: u32,
+    #[serde(default)]
+    pub escrow_received_count: u32,
+    #[serde(default)]
+    pub escrow_sent_count: u32,
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct EscrowData {
     pub escrow_end_duration_seconds: i64,
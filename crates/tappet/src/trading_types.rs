@@ -40,7 +40,7 @@ pub enum ETradeStatus {
     EscrowRollback = 11,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct Descriptions {
     pub appid: u32,
     #[serde(with = "serde_with::rust::display_fromstr")]
@@ -49,6 +49,8 @@ pub struct Descriptions {
     pub instanceid: u32,
     pub marketable: bool,
     pub tradable: bool,
+    pub name: Option<String>,
+    pub market_hash_name: Option<String>,
 }
 
 #[allow(non_camel_case_types)]
@@ -75,6 +77,8 @@ pub struct GetTradeOffersResponse {
 pub struct CEcon_GetTradeOffers_Response {
     pub trade_offers_sent: Option<Vec<TradeOffer_Trade>>,
     pub trade_offers_received: Option<Vec<TradeOffer_Trade>>,
+    /// Descriptions of the items of the offers. Only present if `get_descriptions` was requested.
+    pub descriptions: Option<Vec<Descriptions>>,
 }
 
 #[allow(non_camel_case_types)]
@@ -94,12 +98,16 @@ pub struct TradeOffer_Trade {
     /// State of trade offer
     #[serde(rename = "trade_offer_state")]
     pub state: ETradeOfferState,
-    items_to_give: Option<Vec<CEcon_Asset>>,
-    items_to_receive: Option<Vec<CEcon_Asset>>,
+    /// Items that will be given by the account binded with the api key, if the offer is accepted.
+    pub items_to_give: Option<Vec<CEcon_Asset>>,
+    /// Items that will be received by the account binded with the api key, if the offer is accepted.
+    pub items_to_receive: Option<Vec<CEcon_Asset>>,
     /// Indicates the account binded with the api key requested this trade
     pub is_our_offer: bool,
-    time_created: i64,
-    time_updated: i64,
+    /// Unix time when the offer was created
+    pub time_created: i64,
+    /// Unix time when the offer state last changed
+    pub time_updated: i64,
     /// Tradeid is the historical number of the trade.
     /// It is used, for example to find the new generated asset ids after the trade is completed.
     ///
@@ -142,6 +150,27 @@ pub struct GetTradeHistoryResponse {
 pub struct CEcon_GetTradeHistory_Response_Trade_Intermediate {
     pub more: bool,
     pub trades: Vec<TradeHistory_Trade>,
+    /// Total of trades of the account. Only present if `include_total` was requested.
+    pub total_trades: Option<u32>,
+    /// Descriptions of the traded items. Only present if `get_descriptions` was requested.
+    pub descriptions: Option<Vec<Descriptions>>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+/// Represents the raw CEcon_GetTradeStatus_Response
+pub struct GetTradeStatusResponse {
+    pub response: CEcon_GetTradeStatus_Response,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CEcon_GetTradeStatus_Response {
+    /// The requested trade, in the same format as GetTradeHistory. Empty if the trade was not found.
+    #[serde(default)]
+    pub trades: Vec<TradeHistory_Trade>,
+    /// Descriptions of the traded items. Only present if `get_descriptions` was requested.
+    pub descriptions: Option<Vec<Descriptions>>,
 }
 
 #[allow(non_camel_case_types)]